@@ -2,7 +2,12 @@
 
 import initWasm, {
   addressFromPublicKey,
+  devAccount,
+  devKeypair,
   keypairFromSecret,
+  keypairFromSuri,
+  keyType,
+  phraseLanguage,
   phraseSize,
   newPhrase,
   secretFromPhrase,
@@ -62,7 +67,7 @@ export default class Account {
 
     const account = new Account();
     await initWasm();
-    account.#phrase = newPhrase(numWords);
+    account.#phrase = newPhrase(numWords, phraseLanguage.English);
     account.#secret = secretFromPhrase(account.#phrase, "");
     account.#keypair = keypairFromSecret(account.#secret);
     return account;
@@ -95,6 +100,31 @@ export default class Account {
     return account;
   }
 
+  /**
+   * Generate a new account backed by the specified secret URI (e.g. `//Alice` or `phrase//hard/soft`)
+   * @param {string} suri
+   * @returns a new account backed by the keypair derived from the specified secret URI
+   */
+  static async fromSuri(suri = "//Alice") {
+    const account = new Account();
+    await initWasm();
+    account.#keypair = keypairFromSuri(suri);
+    return account;
+  }
+
+  /**
+   * Create a well-known development Account (e.g. Alice or Bob's stash)
+   * @param {number} name a devAccount value
+   * @param {boolean} stash whether to use the //stash variant of the account
+   * @returns a new sr25519 Account derived from the development phrase
+   */
+  static async dev(name = devAccount.Alice, stash = false) {
+    const account = new Account();
+    await initWasm();
+    account.#keypair = devKeypair(name, stash, keyType.Sr25519);
+    return account;
+  }
+
   get phrase() {
     return this.#phrase;
   }
@@ -0,0 +1,288 @@
+use error::Error;
+use parity_scale_codec::Encode;
+use wasm_bindgen::prelude::*;
+
+use crate::{address_from_public_key, blake2_256};
+
+/// Compute the account ID of a pallet-multisig account
+///
+/// signatories: 32-byte account IDs of the signatories, in any order
+/// threshold: number of signatories that must approve a call
+///
+/// Returns the 32-byte account ID of the multisig account
+///
+/// ref: https://github.com/paritytech/substrate/blob/monthly-2022-09/frame/multisig/src/lib.rs
+pub fn multi_account_id(signatories: &[[u8; 32]], threshold: u16) -> [u8; 32] {
+    let mut signatories = signatories.to_vec();
+    signatories.sort();
+
+    blake2_256(&(b"modlpy/utilisuba", signatories, threshold).encode())
+}
+
+/// Create the Ss58 address of a pallet-multisig account
+///
+/// public_keys: concatenation of the signatories' 32-byte public keys, in any order
+/// threshold: number of signatories that must approve a call
+/// format: Ss58 format
+///
+/// Returns the Ss58 address of the multisig account
+#[wasm_bindgen(js_name = multisigAddress)]
+pub fn multisig_address(public_keys: &[u8], threshold: u16, format: u16) -> Result<String, Error> {
+    // `usize::is_multiple_of` needs Rust 1.87
+    #[allow(clippy::manual_is_multiple_of)]
+    if public_keys.is_empty() || public_keys.len() % 32 != 0 {
+        return Err(Error::InvalidKey);
+    }
+
+    let signatories: Vec<[u8; 32]> = public_keys
+        .chunks(32)
+        .map(|k| k.try_into().expect("chunks are 32 bytes"))
+        .collect();
+
+    address_from_public_key(&multi_account_id(&signatories, threshold), format)
+}
+
+/// Decode a 32-byte account ID (i.e. public key)
+fn account_id(public_key: &[u8]) -> Result<[u8; 32], Error> {
+    public_key.try_into().map_err(|_| Error::InvalidKey)
+}
+
+/// Interpret encoded data as an account ID by truncating or zero-padding it to 32 bytes
+///
+/// ref: https://github.com/paritytech/substrate/blob/monthly-2022-09/primitives/runtime/src/traits.rs
+fn into_account_truncating(encoded: &[u8]) -> [u8; 32] {
+    let mut account = [0u8; 32];
+    let len = encoded.len().min(32);
+    account[..len].copy_from_slice(&encoded[..len]);
+    account
+}
+
+/// Compute the account ID of a `PalletId` (e.g. `py/trsry` for the treasury)
+///
+/// pallet_id: 8-byte pallet ID
+/// sub_account: index of the sub-account, if any
+///
+/// Returns the 32-byte account ID of the pallet (or its sub-account)
+///
+/// ref: https://github.com/paritytech/substrate/blob/monthly-2022-09/frame/support/src/lib.rs
+pub fn pallet_account_id(pallet_id: &[u8; 8], sub_account: Option<u32>) -> [u8; 32] {
+    match sub_account {
+        Some(sub) => into_account_truncating(&(b"modl", pallet_id, sub).encode()),
+        None => into_account_truncating(&(b"modl", pallet_id).encode()),
+    }
+}
+
+/// Create the Ss58 address of a `PalletId`
+///
+/// pallet_id: 8-byte pallet ID
+/// sub_account: index of the sub-account, if any
+/// format: Ss58 format
+///
+/// Returns the Ss58 address of the pallet (or its sub-account)
+#[wasm_bindgen(js_name = palletAddress)]
+pub fn pallet_address(
+    pallet_id: &[u8],
+    sub_account: Option<u32>,
+    format: u16,
+) -> Result<String, Error> {
+    let pallet_id = pallet_id.try_into().map_err(|_| Error::InvalidPalletId)?;
+    address_from_public_key(&pallet_account_id(pallet_id, sub_account), format)
+}
+
+/// Compute the account ID of a pallet-utility `as_derivative` sub-account
+///
+/// who: 32-byte account ID of the parent account
+/// index: index of the sub-account
+///
+/// Returns the 32-byte account ID of the sub-account
+///
+/// ref: https://github.com/paritytech/substrate/blob/monthly-2022-09/frame/utility/src/lib.rs
+pub fn derivative_account_id(who: &[u8; 32], index: u16) -> [u8; 32] {
+    blake2_256(&(b"modlpy/utilisuba", who, index).encode())
+}
+
+/// Create the Ss58 address of a pallet-utility `as_derivative` sub-account
+///
+/// public_key: 32-byte public key of the parent account
+/// index: index of the sub-account
+/// format: Ss58 format
+///
+/// Returns the Ss58 address of the sub-account
+#[wasm_bindgen(js_name = derivativeAddress)]
+pub fn derivative_address(public_key: &[u8], index: u16, format: u16) -> Result<String, Error> {
+    address_from_public_key(
+        &derivative_account_id(&account_id(public_key)?, index),
+        format,
+    )
+}
+
+/// Compute the account ID of a pallet-proxy pure (formerly "anonymous") proxy
+///
+/// spawner: 32-byte account ID of the account that created the pure proxy
+/// proxy_type: index of the proxy type variant in the runtime's `ProxyType` enum
+/// index: disambiguation index that was passed to `create_pure`
+/// height: number of the block in which the pure proxy was created
+/// extrinsic_index: index of the `create_pure` extrinsic within its block
+///
+/// Returns the 32-byte account ID of the pure proxy
+///
+/// ref: https://github.com/paritytech/substrate/blob/monthly-2022-09/frame/proxy/src/lib.rs
+pub fn pure_proxy_account_id(
+    spawner: &[u8; 32],
+    proxy_type: u8,
+    index: u16,
+    height: u32,
+    extrinsic_index: u32,
+) -> [u8; 32] {
+    blake2_256(
+        &(
+            b"modlpy/proxy____",
+            spawner,
+            height,
+            extrinsic_index,
+            proxy_type,
+            index,
+        )
+            .encode(),
+    )
+}
+
+/// Create the Ss58 address of a pallet-proxy pure proxy
+///
+/// public_key: 32-byte public key of the account that created the pure proxy
+/// proxy_type: index of the proxy type variant in the runtime's `ProxyType` enum
+/// index: disambiguation index that was passed to `create_pure`
+/// height: number of the block in which the pure proxy was created
+/// extrinsic_index: index of the `create_pure` extrinsic within its block
+/// format: Ss58 format
+///
+/// Returns the Ss58 address of the pure proxy
+#[wasm_bindgen(js_name = pureProxyAddress)]
+pub fn pure_proxy_address(
+    public_key: &[u8],
+    proxy_type: u8,
+    index: u16,
+    height: u32,
+    extrinsic_index: u32,
+    format: u16,
+) -> Result<String, Error> {
+    let spawner = account_id(public_key)?;
+    address_from_public_key(
+        &pure_proxy_account_id(&spawner, proxy_type, index, height, extrinsic_index),
+        format,
+    )
+}
+
+/// Compute the sovereign account ID of a parachain
+///
+/// para_id: ID of the parachain
+/// sibling: whether the account is the parachain's sovereign account on a sibling parachain
+///   (`sibl`) rather than on its relay chain (`para`)
+///
+/// Returns the 32-byte sovereign account ID of the parachain
+///
+/// ref: https://github.com/paritytech/polkadot/blob/v0.9.29/parachain/src/primitives.rs
+pub fn parachain_account_id(para_id: u32, sibling: bool) -> [u8; 32] {
+    let prefix = if sibling { b"sibl" } else { b"para" };
+    into_account_truncating(&(prefix, para_id).encode())
+}
+
+/// Create the Ss58 address of a parachain's sovereign account
+///
+/// para_id: ID of the parachain
+/// sibling: whether the account is the parachain's sovereign account on a sibling parachain
+///   rather than on its relay chain
+/// format: Ss58 format
+///
+/// Returns the Ss58 address of the parachain's sovereign account
+#[wasm_bindgen(js_name = parachainAddress)]
+pub fn parachain_address(para_id: u32, sibling: bool, format: u16) -> Result<String, Error> {
+    address_from_public_key(&parachain_account_id(para_id, sibling), format)
+}
+
+#[cfg(test)]
+mod tests {
+    use error::Error;
+
+    use crate::{
+        derivative_address, keypair_from_suri, multisig_address, pallet_address,
+        parachain_account_id, parachain_address, pure_proxy_address,
+    };
+
+    #[test]
+    fn derives_multisig_address() {
+        let alice = keypair_from_suri("//Alice").unwrap();
+        let bob = keypair_from_suri("//Bob").unwrap();
+        let charlie = keypair_from_suri("//Charlie").unwrap();
+
+        let forward = [&alice[64..], &bob[64..], &charlie[64..]].concat();
+        let backward = [&charlie[64..], &bob[64..], &alice[64..]].concat();
+
+        assert_eq!(
+            multisig_address(&forward, 2, 42).unwrap(),
+            "5DjYJStmdZ2rcqXbXGX7TW85JsrW6uG4y9MUcLq2BoPMpRA7"
+        );
+        assert_eq!(
+            multisig_address(&forward, 2, 42),
+            multisig_address(&backward, 2, 42)
+        );
+        assert_ne!(
+            multisig_address(&forward, 2, 42),
+            multisig_address(&forward, 3, 42)
+        );
+    }
+
+    #[test]
+    fn derives_pallet_addresses() {
+        assert_eq!(
+            pallet_address(b"py/trsry", None, 42).unwrap(),
+            "5EYCAe5ijiYfyeZ2JJCGq56LmPyNRAKzpG4QkoQkkQNB5e6Z"
+        );
+        assert_ne!(
+            pallet_address(b"py/trsry", Some(1), 42),
+            pallet_address(b"py/trsry", None, 42)
+        );
+        assert_eq!(
+            pallet_address(b"py/trsry!", None, 42),
+            Err(Error::InvalidPalletId)
+        );
+
+        assert_eq!(
+            hex::encode(parachain_account_id(1_000, false)),
+            "70617261e8030000000000000000000000000000000000000000000000000000"
+        );
+        assert_eq!(
+            hex::encode(parachain_account_id(1_000, true)),
+            "7369626ce8030000000000000000000000000000000000000000000000000000"
+        );
+        assert_eq!(
+            parachain_address(1_000, false, 0).unwrap(),
+            "13YMK2edbuhwMBxeUWm9c643A2wyYHwSVh1bCM7tShtg7Dtk"
+        );
+    }
+
+    #[test]
+    fn derives_sub_accounts() {
+        let alice = keypair_from_suri("//Alice").unwrap();
+
+        // checked against blake2b-256 of the SCALE encoding that the pallets hash
+        assert_eq!(
+            derivative_address(&alice[64..], 0, 42).unwrap(),
+            "5Ep769A4Ka6QrHYoPfzA1fTWRSXpf28vhdbWHWmkWmi4SNHi"
+        );
+        assert_eq!(
+            derivative_address(&alice[64..], 1, 42).unwrap(),
+            "5HfyUeY7jWfArT21FcynErXqZUDBgHirZsSkZsQVje9Ner6m"
+        );
+
+        // proxy type 1, index 2, created by the 4th extrinsic of block 100
+        assert_eq!(
+            pure_proxy_address(&alice[64..], 1, 2, 100, 3, 42).unwrap(),
+            "5Fy7qVkQR2WRpJYwfjjSQ3WNe8QJtKQkckPmofpGscBpRk8R"
+        );
+        assert_eq!(
+            pure_proxy_address(&alice[64..], 0, 0, 1, 1, 42).unwrap(),
+            "5FsKgGwGt2osJNkig3ENQ1wmhn2AtAFyNzqDeaJMFiurBpeL"
+        );
+    }
+}
@@ -0,0 +1,59 @@
+use error::Error;
+
+/// Offset of hardened child indices
+pub(crate) const HARDENED: u32 = 0x8000_0000;
+
+/// Parse a bip32 derivation path of the form `m/44'/354'/0'/0'/0'`
+///
+/// Hardened indices are marked with a trailing `'` (or `h`) and are returned with `HARDENED` added
+pub(crate) fn parse_path(path: &str) -> Result<Vec<u32>, Error> {
+    let mut segments = path.split('/');
+    if segments.next() != Some("m") {
+        return Err(Error::InvalidPath);
+    }
+
+    segments
+        .map(|segment| {
+            let (index, hardened) = match segment.strip_suffix(['\'', 'h']) {
+                Some(index) => (index, true),
+                None => (segment, false),
+            };
+
+            if index.is_empty() || !index.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(Error::InvalidPath);
+            }
+
+            match (index.parse::<u32>(), hardened) {
+                (Ok(i), false) if i < HARDENED => Ok(i),
+                (Ok(i), true) if i < HARDENED => Ok(i + HARDENED),
+                _ => Err(Error::InvalidPath),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use error::Error;
+
+    use super::{parse_path, HARDENED};
+
+    #[test]
+    fn parses_paths() {
+        assert_eq!(
+            parse_path("m/44'/354'/0'/0/1h"),
+            Ok(vec![
+                44 + HARDENED,
+                354 + HARDENED,
+                HARDENED,
+                0,
+                1 + HARDENED
+            ])
+        );
+        assert_eq!(parse_path("m"), Ok(vec![]));
+
+        for path in ["", "44'/0'", "m/", "m/-1", "m/0''", "m/2147483648", "m/x'"] {
+            assert_eq!(parse_path(path), Err(Error::InvalidPath));
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use error::Error;
+use wasm_bindgen::prelude::*;
+
+use crate::{address_from_public_key, ecdsa, ed25519, keypair_from_suri, KeyType};
+
+/// Well-known development accounts that are derived from the development phrase
+#[wasm_bindgen(js_name = devAccount)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DevAccount {
+    Alice,
+    Bob,
+    Charlie,
+    Dave,
+    Eve,
+    Ferdie,
+}
+
+impl DevAccount {
+    pub const ALL: [DevAccount; 6] = [
+        DevAccount::Alice,
+        DevAccount::Bob,
+        DevAccount::Charlie,
+        DevAccount::Dave,
+        DevAccount::Eve,
+        DevAccount::Ferdie,
+    ];
+
+    /// The secret URI of the account, e.g. `//Alice` or `//Alice//stash`
+    pub fn suri(&self, stash: bool) -> String {
+        let name = match self {
+            DevAccount::Alice => "Alice",
+            DevAccount::Bob => "Bob",
+            DevAccount::Charlie => "Charlie",
+            DevAccount::Dave => "Dave",
+            DevAccount::Eve => "Eve",
+            DevAccount::Ferdie => "Ferdie",
+        };
+
+        match stash {
+            true => format!("//{}//stash", name),
+            false => format!("//{}", name),
+        }
+    }
+}
+
+/// Create the keypair of a development account
+///
+/// * account: development account
+/// * stash: whether to create the `//stash` variant of the account
+/// * key_type: type of keypair to create
+///
+/// Returns a keypair with the same layout as the keypairs that are created from secret URIs for
+/// the key type
+///
+/// ref: https://github.com/paritytech/substrate/blob/monthly-2022-09/primitives/keyring/src/sr25519.rs
+#[wasm_bindgen(js_name = devKeypair)]
+pub fn dev_keypair(account: DevAccount, stash: bool, key_type: KeyType) -> Result<Vec<u8>, Error> {
+    let suri = account.suri(stash);
+    match key_type {
+        KeyType::Sr25519 => keypair_from_suri(&suri),
+        KeyType::Ed25519 => ed25519::keypair_from_suri(&suri),
+        KeyType::Ecdsa => ecdsa::keypair_from_suri(&suri),
+    }
+}
+
+/// Get the Ss58 address of a development account
+///
+/// * account: development account
+/// * stash: whether to use the `//stash` variant of the account
+/// * key_type: type of keypair that backs the account
+/// * format: Ss58 format of the address
+///
+/// Returns the Ss58 address of the account
+#[wasm_bindgen(js_name = devAddress)]
+pub fn dev_address(
+    account: DevAccount,
+    stash: bool,
+    key_type: KeyType,
+    format: u16,
+) -> Result<String, Error> {
+    let keypair = dev_keypair(account, stash, key_type)?;
+    let (private_length, _) = key_type.key_lengths();
+    let public_key = &keypair[private_length..];
+
+    match key_type {
+        KeyType::Ecdsa => ecdsa::address(public_key, format),
+        _ => address_from_public_key(public_key, format),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{dev_address, dev_keypair, keypair_from_secret, DevAccount, KeyType};
+
+    #[test]
+    fn derives_dev_accounts() {
+        let alice = dev_keypair(DevAccount::Alice, false, KeyType::Sr25519).unwrap();
+        let secret =
+            hex::decode("e5be9a5092b81bca64be81d212e7f2f9eba183bb7a90954f7b76361f6edb5c0a")
+                .unwrap();
+        assert_eq!(alice, keypair_from_secret(&secret).unwrap());
+
+        let addresses = [
+            (
+                DevAccount::Bob,
+                false,
+                KeyType::Sr25519,
+                "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty",
+            ),
+            (
+                DevAccount::Alice,
+                true,
+                KeyType::Sr25519,
+                "5GNJqTPyNqANBkUVMN1LPPrxXnFouWXoe2wNSmmEoLctxiZY",
+            ),
+            (
+                DevAccount::Alice,
+                false,
+                KeyType::Ed25519,
+                "5FA9nQDVg267DEd8m1ZypXLBnvN7SFxYwV7ndqSYGiN9TTpu",
+            ),
+            (
+                DevAccount::Bob,
+                false,
+                KeyType::Ed25519,
+                "5GoNkf6WdbxCFnPdAnYYQyCjAKPJgLNxXwPjwTh6DGg6gN3E",
+            ),
+        ];
+        for (account, stash, key_type, address) in addresses {
+            assert_eq!(dev_address(account, stash, key_type, 42).unwrap(), address);
+        }
+
+        for account in DevAccount::ALL {
+            for key_type in [KeyType::Sr25519, KeyType::Ed25519, KeyType::Ecdsa] {
+                assert_ne!(
+                    dev_address(account, false, key_type, 42).unwrap(),
+                    dev_address(account, true, key_type, 42).unwrap()
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,390 @@
+use error::Error;
+use hmac::{Hmac, Mac};
+use libsecp256k1::{Message, PublicKey, RecoveryId, SecretKey, Signature};
+use parity_scale_codec::Encode;
+use sha2::Sha512;
+use sha3::{Digest, Keccak256};
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    address_from_public_key, bip32, blake2_256, seed_from_phrase,
+    suri::{DeriveJunction, Suri},
+    SeedMode,
+};
+
+/// The hash function that is applied to a message before it is signed
+#[wasm_bindgen(js_name = ecdsaHasher)]
+pub enum Hasher {
+    /// Used by Substrate's `MultiSignature::Ecdsa`
+    Blake2_256,
+    /// Used by Ethereum-compatible chains
+    Keccak256,
+}
+
+impl Hasher {
+    fn hash(&self, message: &[u8]) -> [u8; 32] {
+        match self {
+            Hasher::Blake2_256 => blake2_256(message),
+            Hasher::Keccak256 => Keccak256::digest(message).into(),
+        }
+    }
+}
+
+/// Create an ECDSA (secp256k1) keypair from a secret
+///
+/// secret: 32-byte secret
+///
+/// Returns a 65-byte vector that is the result of appending the 33-byte compressed public key to
+/// the end of the 32-byte private key
+///
+/// ref: https://github.com/paritytech/substrate/blob/monthly-2022-09/primitives/core/src/ecdsa.rs#L395:L407
+#[wasm_bindgen(js_name = ecdsaKeypairFromSecret)]
+pub fn keypair_from_secret(secret: &[u8]) -> Result<Vec<u8>, Error> {
+    let secret = match SecretKey::parse_slice(secret) {
+        Ok(s) => s,
+        _ => return Err(Error::InvalidSecret),
+    };
+
+    let mut keypair = secret.serialize().to_vec();
+    keypair.extend_from_slice(&PublicKey::from_secret_key(&secret).serialize_compressed());
+    Ok(keypair)
+}
+
+/// Create an ECDSA (secp256k1) keypair from a secret URI
+///
+/// suri: secret URI of the form `phrase//hard///password`; ECDSA only supports hard junctions
+///
+/// Returns a 65-byte vector that is the result of appending the 33-byte compressed public key to
+/// the end of the 32-byte private key of the derived keypair
+///
+/// ref: https://github.com/paritytech/substrate/blob/monthly-2022-09/primitives/core/src/ecdsa.rs#L434:L449
+#[wasm_bindgen(js_name = ecdsaKeypairFromSuri)]
+pub fn keypair_from_suri(suri: &str) -> Result<Vec<u8>, Error> {
+    let suri = Suri::parse(suri)?;
+    let seed = suri
+        .path
+        .iter()
+        .try_fold(suri.secret()?, |seed, junction| match junction {
+            DeriveJunction::Hard(cc) => Ok(derive_hard_junction(&seed, cc)),
+            DeriveJunction::Soft(_) => Err(Error::UnsupportedDerivation),
+        })?;
+
+    keypair_from_secret(&seed)
+}
+
+/// Hard-derive a child seed from a seed and a chain code
+fn derive_hard_junction(seed: &[u8], cc: &[u8; 32]) -> Vec<u8> {
+    let mut data = "Secp256k1HDKD".encode();
+    data.extend_from_slice(seed);
+    data.extend_from_slice(cc);
+
+    blake2_256(&data).to_vec()
+}
+
+/// Sign a message
+///
+/// * privkey: 32-byte private key
+/// * message: message to be hashed and signed
+/// * hasher: hash function to apply to the message
+///
+/// * Returns a 65-byte recoverable signature (i.e. `r ++ s ++ v`)
+#[wasm_bindgen(js_name = ecdsaSign)]
+pub fn sign(privkey: &[u8], message: &[u8], hasher: Hasher) -> Result<Vec<u8>, Error> {
+    let secret = match SecretKey::parse_slice(privkey) {
+        Ok(s) => s,
+        _ => return Err(Error::InvalidKey),
+    };
+
+    let (signature, recovery_id) =
+        libsecp256k1::sign(&Message::parse(&hasher.hash(message)), &secret);
+
+    let mut bytes = signature.serialize().to_vec();
+    bytes.push(recovery_id.serialize());
+    Ok(bytes)
+}
+
+/// Verify a signature
+///
+/// * pubkey: 33-byte compressed public key
+/// * message: message that was hashed and signed
+/// * signature: 65-byte recoverable signature
+/// * hasher: hash function that was applied to the message
+///
+/// * Returns true if the public key recovered from the signature matches the given public key
+#[wasm_bindgen(js_name = ecdsaVerify)]
+pub fn verify(pubkey: &[u8], message: &[u8], signature: &[u8], hasher: Hasher) -> bool {
+    if signature.len() != 65 {
+        return false;
+    }
+
+    match (
+        Signature::parse_standard_slice(&signature[..64]),
+        RecoveryId::parse(signature[64]),
+    ) {
+        (Ok(s), Ok(r)) => libsecp256k1::recover(&Message::parse(&hasher.hash(message)), &s, &r)
+            .map(|k| k.serialize_compressed()[..] == *pubkey)
+            .unwrap_or(false),
+        _ => false,
+    }
+}
+
+/// Create an Ss58 address from a compressed ECDSA public key and an Ss58 format
+///
+/// public_key: 33-byte compressed public key
+/// format: Ss58 format
+///
+/// Returns the Ss58 address of the blake2b-256 hash of the public key
+///
+/// ref: https://github.com/paritytech/substrate/blob/monthly-2022-09/primitives/runtime/src/traits.rs#L120:L124
+#[wasm_bindgen(js_name = ecdsaAddressFromPublicKey)]
+pub fn address(public_key: &[u8], format: u16) -> Result<String, Error> {
+    address_from_public_key(&blake2_256(public_key), format)
+}
+
+/// Create an Ethereum (H160) address from an ECDSA public key
+///
+/// public_key: 33-byte compressed or 65-byte uncompressed public key
+///
+/// Returns the last 20 bytes of the keccak-256 hash of the uncompressed public key (sans prefix)
+#[wasm_bindgen(js_name = ethereumAddressFromPublicKey)]
+pub fn ethereum_address(public_key: &[u8]) -> Result<Vec<u8>, Error> {
+    let public = match PublicKey::parse_slice(public_key, None) {
+        Ok(k) => k,
+        _ => return Err(Error::InvalidKey),
+    };
+
+    Ok(Keccak256::digest(&public.serialize()[1..])[12..].to_vec())
+}
+
+/// Create an Ethereum (H160) address with an EIP-55 mixed-case checksum
+///
+/// address: 20-byte address
+///
+/// Returns the `0x`-prefixed checksummed address
+///
+/// ref: https://eips.ethereum.org/EIPS/eip-55
+#[wasm_bindgen(js_name = ethereumChecksumAddress)]
+pub fn checksum_address(address: &[u8]) -> Result<String, Error> {
+    if address.len() != 20 {
+        return Err(Error::InvalidAddressLength);
+    }
+
+    let address = hex::encode(address);
+    let hash = Keccak256::digest(address.as_bytes());
+
+    let checksummed: String = address
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            let nibble = (hash[i / 2] >> (4 * (1 - i % 2))) & 0x0f;
+            match nibble >= 8 {
+                true => c.to_ascii_uppercase(),
+                false => c,
+            }
+        })
+        .collect();
+
+    Ok(format!("0x{}", checksummed))
+}
+
+/// An account that is derived with bip32 from a bip39 phrase, e.g. by MetaMask
+#[wasm_bindgen]
+pub struct EthereumAccount {
+    private_key: Vec<u8>,
+    public_key: Vec<u8>,
+    uncompressed_public_key: Vec<u8>,
+    address: String,
+}
+
+#[wasm_bindgen]
+impl EthereumAccount {
+    /// 32-byte private key
+    #[wasm_bindgen(getter, js_name = privateKey)]
+    pub fn private_key(&self) -> Vec<u8> {
+        self.private_key.clone()
+    }
+
+    /// 33-byte compressed public key
+    #[wasm_bindgen(getter, js_name = publicKey)]
+    pub fn public_key(&self) -> Vec<u8> {
+        self.public_key.clone()
+    }
+
+    /// 65-byte uncompressed public key
+    #[wasm_bindgen(getter, js_name = uncompressedPublicKey)]
+    pub fn uncompressed_public_key(&self) -> Vec<u8> {
+        self.uncompressed_public_key.clone()
+    }
+
+    /// EIP-55 checksummed H160 address
+    #[wasm_bindgen(getter)]
+    pub fn address(&self) -> String {
+        self.address.clone()
+    }
+}
+
+/// Derive a secp256k1 private key from a bip32 seed
+fn derive_bip32(seed: &[u8], path: &[u32]) -> Result<SecretKey, Error> {
+    let mut mac = Hmac::<Sha512>::new_from_slice(b"Bitcoin seed").expect("valid key length");
+    mac.update(seed);
+    let master = mac.finalize().into_bytes();
+
+    let secret = SecretKey::parse_slice(&master[..32]).map_err(|_| Error::InvalidKey)?;
+    let (secret, _) = path.iter().try_fold(
+        (secret, master[32..].to_vec()),
+        |(secret, chain_code), index| {
+            let mut mac = Hmac::<Sha512>::new_from_slice(&chain_code).expect("valid key length");
+            match *index >= bip32::HARDENED {
+                true => {
+                    mac.update(&[0]);
+                    mac.update(&secret.serialize());
+                }
+                false => mac.update(&PublicKey::from_secret_key(&secret).serialize_compressed()),
+            }
+            mac.update(&index.to_be_bytes());
+            let child = mac.finalize().into_bytes();
+
+            // the child key is invalid if the tweak is not less than the order of the curve or if
+            // the resulting key is zero, which both happen with a probability lower than 1 in 2^127
+            let mut child_secret = secret;
+            SecretKey::parse_slice(&child[..32])
+                .and_then(|tweak| child_secret.tweak_add_assign(&tweak))
+                .map_err(|_| Error::InvalidKey)?;
+
+            Ok((child_secret, child[32..].to_vec()))
+        },
+    )?;
+
+    Ok(secret)
+}
+
+/// Derive an account from a bip39 phrase along a bip32 path
+///
+/// * phrase: mnemonic phrase
+/// * password: bip39 password, which is empty by default
+/// * path: bip32 derivation path (e.g. `m/44'/60'/0'/0/0`)
+///
+/// Returns the derived account
+///
+/// ref: https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki
+#[wasm_bindgen(js_name = ethereumAccountFromPhrase)]
+pub fn account_from_phrase(
+    phrase: &str,
+    password: &str,
+    path: &str,
+) -> Result<EthereumAccount, Error> {
+    let seed = seed_from_phrase(phrase, password, SeedMode::Bip39)?;
+    let secret = derive_bip32(&seed, &bip32::parse_path(path)?)?;
+    let public = PublicKey::from_secret_key(&secret);
+
+    Ok(EthereumAccount {
+        private_key: secret.serialize().to_vec(),
+        public_key: public.serialize_compressed().to_vec(),
+        uncompressed_public_key: public.serialize().to_vec(),
+        address: checksum_address(&ethereum_address(&public.serialize())?)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        bip32,
+        ecdsa::{self, Hasher},
+    };
+
+    #[test]
+    fn derives_alice() {
+        let alice = ecdsa::keypair_from_suri("//Alice").unwrap();
+
+        assert_eq!(
+            hex::encode(&alice[32..]),
+            "020a1091341fe5664bfa1782d5e04779689068c916b04cb365ec3153755684d9a1"
+        );
+
+        let signature = ecdsa::sign(&alice[..32], b"dots", Hasher::Keccak256).unwrap();
+        assert!(ecdsa::verify(
+            &alice[32..],
+            b"dots",
+            &signature,
+            Hasher::Keccak256
+        ));
+        assert!(!ecdsa::verify(
+            &alice[32..],
+            b"dots",
+            &signature,
+            Hasher::Blake2_256
+        ));
+    }
+
+    #[test]
+    fn ethereum_address() {
+        // private key 0x01
+        let mut secret = [0u8; 32];
+        secret[31] = 1;
+        let keypair = ecdsa::keypair_from_secret(&secret).unwrap();
+
+        assert_eq!(
+            hex::encode(ecdsa::ethereum_address(&keypair[32..]).unwrap()),
+            "7e5f4552091a69125d5dfcb7b8c2659029395bdf"
+        );
+    }
+
+    #[test]
+    fn derives_bip32_keys() {
+        // test vector 1 of bip32
+        let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+        let vectors = [
+            (
+                "m",
+                "e8f32e723decf4051aefac8e2c93c9c5b214313817cdb01a1494b917c8436b35",
+            ),
+            (
+                "m/0'",
+                "edb2e14f9ee77d26dd93b4ecede8d16ed408ce149b6cd80b0715a2d911a0afea",
+            ),
+            (
+                "m/0'/1",
+                "3c6cb8d0f6a264c91ea8b5030fadaa8e538b020f0a387421a12de9319dc93368",
+            ),
+            (
+                "m/0'/1/2'",
+                "cbce0d719ecf7431d88e6a89fa1483e02e35092af60c042b1df2ff59fa424dca",
+            ),
+        ];
+        for (path, private_key) in vectors {
+            let path = bip32::parse_path(path).unwrap();
+            let secret = ecdsa::derive_bip32(&seed, &path).unwrap();
+            assert_eq!(hex::encode(secret.serialize()), private_key);
+        }
+
+        // first account of the default Hardhat/Anvil development phrase
+        let phrase = "test test test test test test test test test test test junk";
+        let account = ecdsa::account_from_phrase(phrase, "", "m/44'/60'/0'/0/0").unwrap();
+        assert_eq!(
+            hex::encode(account.private_key()),
+            "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"
+        );
+        assert_eq!(
+            account.address(),
+            "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266"
+        );
+        assert_eq!(
+            account.uncompressed_public_key()[1..33],
+            account.public_key()[1..]
+        );
+    }
+
+    #[test]
+    fn checksums_addresses() {
+        // EIP-55 test cases
+        for address in [
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+            "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+        ] {
+            let bytes = hex::decode(&address[2..]).unwrap();
+            assert_eq!(ecdsa::checksum_address(&bytes).unwrap(), address);
+        }
+    }
+}
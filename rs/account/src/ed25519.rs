@@ -0,0 +1,151 @@
+use curve25519_dalek_ng::{edwards::CompressedEdwardsY, scalar::Scalar};
+use ed25519_zebra::{Signature, SigningKey, VerificationKey};
+use error::Error;
+use parity_scale_codec::Encode;
+use sha2::{Digest, Sha512};
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    blake2_256,
+    suri::{DeriveJunction, Suri},
+};
+
+/// Create an ed25519 keypair from a secret
+///
+/// secret: 32-byte secret (i.e. seed)
+///
+/// Returns a 64-byte vector that is the result of appending the 32-byte public key to the end of the
+/// 32-byte private key
+///
+/// ref: https://github.com/paritytech/substrate/blob/monthly-2022-09/primitives/core/src/ed25519.rs#L404:L412
+#[wasm_bindgen(js_name = ed25519KeypairFromSecret)]
+pub fn keypair_from_secret(secret: &[u8]) -> Result<Vec<u8>, Error> {
+    let seed: [u8; 32] = match secret.try_into() {
+        Ok(s) => s,
+        _ => return Err(Error::InvalidSecret),
+    };
+
+    let public = VerificationKey::from(&SigningKey::from(seed));
+
+    let mut keypair = seed.to_vec();
+    keypair.extend_from_slice(public.as_ref());
+    Ok(keypair)
+}
+
+/// Create an ed25519 keypair from a secret URI
+///
+/// suri: secret URI of the form `phrase//hard///password`; ed25519 only supports hard junctions
+///
+/// Returns a 64-byte vector that is the result of appending the 32-byte public key to the end of the
+/// 32-byte private key of the derived keypair
+///
+/// ref: https://github.com/paritytech/substrate/blob/monthly-2022-09/primitives/core/src/ed25519.rs#L446:L461
+#[wasm_bindgen(js_name = ed25519KeypairFromSuri)]
+pub fn keypair_from_suri(suri: &str) -> Result<Vec<u8>, Error> {
+    let suri = Suri::parse(suri)?;
+    let seed = suri
+        .path
+        .iter()
+        .try_fold(suri.secret()?, |seed, junction| match junction {
+            DeriveJunction::Hard(cc) => Ok(derive_hard_junction(&seed, cc)),
+            DeriveJunction::Soft(_) => Err(Error::UnsupportedDerivation),
+        })?;
+
+    keypair_from_secret(&seed)
+}
+
+/// Hard-derive a child seed from a seed and a chain code
+fn derive_hard_junction(seed: &[u8], cc: &[u8; 32]) -> Vec<u8> {
+    let mut data = "Ed25519HDKD".encode();
+    data.extend_from_slice(seed);
+    data.extend_from_slice(cc);
+
+    blake2_256(&data).to_vec()
+}
+
+/// Sign a message
+///
+/// * privkey: 32-byte private key
+/// * message: message to be signed
+///
+/// * Returns a 64-byte signature
+#[wasm_bindgen(js_name = ed25519Sign)]
+pub fn sign(privkey: &[u8], message: &[u8]) -> Result<Vec<u8>, Error> {
+    match SigningKey::try_from(privkey) {
+        Ok(s) => Ok(<[u8; 64]>::from(s.sign(message)).to_vec()),
+        _ => Err(Error::InvalidKey),
+    }
+}
+
+/// Verify a signature
+///
+/// * pubkey: 32-byte public key
+/// * message: message that was signed
+/// * signature: 64-byte signature
+///
+/// * Returns true if the signature is valid for the message and public key
+#[wasm_bindgen(js_name = ed25519Verify)]
+pub fn verify(pubkey: &[u8], message: &[u8], signature: &[u8]) -> bool {
+    match (
+        VerificationKey::try_from(pubkey),
+        Signature::try_from(signature),
+    ) {
+        (Ok(k), Ok(s)) => k.verify(&s, message).is_ok(),
+        _ => false,
+    }
+}
+
+/// Compute an X25519 shared secret from a private key and another account's public key
+///
+/// * pubkey: 32-byte public key of the other account
+/// * privkey: 32-byte private key (i.e. seed)
+///
+/// Returns the 32-byte shared secret, which is the same for both accounts
+///
+/// ref: https://www.rfc-editor.org/rfc/rfc7748
+#[wasm_bindgen(js_name = ed25519Agree)]
+pub fn agree(pubkey: &[u8], privkey: &[u8]) -> Result<Vec<u8>, Error> {
+    let public = match pubkey
+        .try_into()
+        .map(|k| CompressedEdwardsY(k).decompress())
+    {
+        Ok(Some(p)) => p.to_montgomery(),
+        _ => return Err(Error::InvalidKey),
+    };
+
+    if privkey.len() != 32 {
+        return Err(Error::InvalidKey);
+    }
+
+    // the clamped scalar from which the ed25519 public key is derived
+    let mut scalar = [0u8; 32];
+    scalar.copy_from_slice(&Sha512::digest(privkey)[..32]);
+    scalar[0] &= 248;
+    scalar[31] &= 127;
+    scalar[31] |= 64;
+
+    let shared = (public * Scalar::from_bits(scalar)).to_bytes();
+    match shared == [0u8; 32] {
+        true => Err(Error::InvalidKey),
+        false => Ok(shared.to_vec()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{address_from_public_key, ed25519};
+
+    #[test]
+    fn derives_alice() {
+        let alice = ed25519::keypair_from_suri("//Alice").unwrap();
+
+        assert_eq!(
+            address_from_public_key(&alice[32..], 42).unwrap(),
+            "5FA9nQDVg267DEd8m1ZypXLBnvN7SFxYwV7ndqSYGiN9TTpu"
+        );
+
+        let signature = ed25519::sign(&alice[..32], b"dots").unwrap();
+        assert!(ed25519::verify(&alice[32..], b"dots", &signature));
+        assert!(!ed25519::verify(&alice[32..], b"tods", &signature));
+    }
+}
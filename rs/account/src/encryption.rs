@@ -0,0 +1,199 @@
+use crypto_secretbox::{
+    aead::{rand_core::RngCore, Aead, KeyInit, OsRng},
+    XSalsa20Poly1305,
+};
+use curve25519_dalek_ng::{ristretto::CompressedRistretto, scalar::Scalar};
+use error::Error;
+use schnorrkel::SecretKey;
+use wasm_bindgen::prelude::*;
+
+use crate::{blake2_256, ed25519, keypair_from_secret, KeyType};
+
+const NONCE_LENGTH: usize = 24;
+
+/// Compute a shared secret from an sr25519 private key and another account's public key
+///
+/// * pubkey: 32-byte public key of the other account
+/// * privkey: 64-byte private key
+///
+/// Returns the 32-byte shared secret (i.e. the compressed Ristretto point), which is the same for
+/// both accounts
+#[wasm_bindgen]
+pub fn agree(pubkey: &[u8], privkey: &[u8]) -> Result<Vec<u8>, Error> {
+    let public = match pubkey
+        .try_into()
+        .map(|k| CompressedRistretto(k).decompress())
+    {
+        Ok(Some(p)) => p,
+        _ => return Err(Error::InvalidKey),
+    };
+
+    let secret = match SecretKey::from_ed25519_bytes(privkey) {
+        Ok(s) => s.to_bytes(),
+        _ => return Err(Error::InvalidKey),
+    };
+
+    let mut key = [0u8; 32];
+    key.copy_from_slice(&secret[..32]);
+    let scalar = Scalar::from_canonical_bytes(key).ok_or(Error::InvalidKey)?;
+
+    let shared = (scalar * public).compress().to_bytes();
+    match shared == [0u8; 32] {
+        true => Err(Error::InvalidKey),
+        false => Ok(shared.to_vec()),
+    }
+}
+
+/// Compute the shared secret of a key type
+fn agree_with(key_type: KeyType, pubkey: &[u8], privkey: &[u8]) -> Result<Vec<u8>, Error> {
+    match key_type {
+        KeyType::Sr25519 => agree(pubkey, privkey),
+        KeyType::Ed25519 => ed25519::agree(pubkey, privkey),
+        KeyType::Ecdsa => Err(Error::UnsupportedKeyType),
+    }
+}
+
+/// Derive the symmetric key of a message from the shared secret and the public keys of both parties
+fn message_key(shared: &[u8], ephemeral: &[u8], recipient: &[u8]) -> [u8; 32] {
+    blake2_256(&[shared, ephemeral, recipient].concat())
+}
+
+/// Encrypt a message so that only the owner of an account can decrypt it
+///
+/// The message is encrypted with XSalsa20-Poly1305 under a key that is agreed between a new
+/// ephemeral keypair and the recipient's public key, so the sender remains anonymous
+///
+/// * pubkey: 32-byte public key of the recipient
+/// * key_type: type of the recipient's keypair; ECDSA is not supported
+/// * message: message to be encrypted
+///
+/// Returns the 32-byte ephemeral public key, followed by the 24-byte nonce and the ciphertext
+#[wasm_bindgen]
+pub fn encrypt(pubkey: &[u8], key_type: KeyType, message: &[u8]) -> Result<Vec<u8>, Error> {
+    let mut secret = [0u8; 32];
+    let mut nonce = [0u8; NONCE_LENGTH];
+    OsRng.fill_bytes(&mut secret);
+    OsRng.fill_bytes(&mut nonce);
+
+    let ephemeral = match key_type {
+        KeyType::Sr25519 => keypair_from_secret(&secret)?,
+        KeyType::Ed25519 => ed25519::keypair_from_secret(&secret)?,
+        KeyType::Ecdsa => return Err(Error::UnsupportedKeyType),
+    };
+    let (private_length, _) = key_type.key_lengths();
+    let (ephemeral_private, ephemeral_public) = ephemeral.split_at(private_length);
+
+    let shared = agree_with(key_type, pubkey, ephemeral_private)?;
+    let key = message_key(&shared, ephemeral_public, pubkey);
+
+    let ciphertext = XSalsa20Poly1305::new(&key.into())
+        .encrypt(&nonce.into(), message)
+        .map_err(|_| Error::InvalidKey)?;
+
+    Ok([ephemeral_public, &nonce, &ciphertext].concat())
+}
+
+/// Decrypt a message that was encrypted for an account
+///
+/// * pubkey: 32-byte public key of the recipient
+/// * privkey: private key of the recipient
+/// * key_type: type of the recipient's keypair
+/// * encrypted: result of `encrypt`
+///
+/// Returns the decrypted message
+#[wasm_bindgen]
+pub fn decrypt(
+    pubkey: &[u8],
+    privkey: &[u8],
+    key_type: KeyType,
+    encrypted: &[u8],
+) -> Result<Vec<u8>, Error> {
+    if key_type == KeyType::Ecdsa {
+        return Err(Error::UnsupportedKeyType);
+    }
+    if privkey.len() != key_type.key_lengths().0 {
+        return Err(Error::InvalidKey);
+    }
+    if encrypted.len() < 32 + NONCE_LENGTH {
+        return Err(Error::InvalidCiphertext);
+    }
+
+    let (ephemeral_public, rest) = encrypted.split_at(32);
+    let (nonce, ciphertext) = rest.split_at(NONCE_LENGTH);
+
+    // the private key is known to be well-formed, so the ephemeral public key must be invalid
+    let shared =
+        agree_with(key_type, ephemeral_public, privkey).map_err(|_| Error::InvalidCiphertext)?;
+    let key = message_key(&shared, ephemeral_public, pubkey);
+
+    XSalsa20Poly1305::new(&key.into())
+        .decrypt(nonce.into(), ciphertext)
+        .map_err(|_| Error::InvalidCiphertext)
+}
+
+#[cfg(test)]
+mod tests {
+    use error::Error;
+
+    use crate::{agree, decrypt, ecdsa, ed25519, encrypt, keypair_from_suri, KeyType};
+
+    #[test]
+    fn agrees_on_secrets() {
+        let alice = keypair_from_suri("//Alice").unwrap();
+        let bob = keypair_from_suri("//Bob").unwrap();
+        assert_eq!(
+            agree(&bob[64..], &alice[..64]).unwrap(),
+            agree(&alice[64..], &bob[..64]).unwrap()
+        );
+
+        let alice = ed25519::keypair_from_suri("//Alice").unwrap();
+        let bob = ed25519::keypair_from_suri("//Bob").unwrap();
+        assert_eq!(
+            ed25519::agree(&bob[32..], &alice[..32]).unwrap(),
+            ed25519::agree(&alice[32..], &bob[..32]).unwrap()
+        );
+    }
+
+    #[test]
+    fn encrypts_messages() {
+        for (key_type, alice, bob) in [
+            (
+                KeyType::Sr25519,
+                keypair_from_suri("//Alice").unwrap(),
+                keypair_from_suri("//Bob").unwrap(),
+            ),
+            (
+                KeyType::Ed25519,
+                ed25519::keypair_from_suri("//Alice").unwrap(),
+                ed25519::keypair_from_suri("//Bob").unwrap(),
+            ),
+        ] {
+            let (private_length, _) = key_type.key_lengths();
+            let (alice_private, alice_public) = alice.split_at(private_length);
+            let (bob_private, bob_public) = bob.split_at(private_length);
+
+            let encrypted = encrypt(alice_public, key_type, b"dots").unwrap();
+            assert_eq!(
+                decrypt(alice_public, alice_private, key_type, &encrypted).unwrap(),
+                b"dots"
+            );
+            assert_eq!(
+                decrypt(bob_public, bob_private, key_type, &encrypted),
+                Err(Error::InvalidCiphertext)
+            );
+
+            let mut tampered = encrypted.clone();
+            *tampered.last_mut().unwrap() ^= 1;
+            assert_eq!(
+                decrypt(alice_public, alice_private, key_type, &tampered),
+                Err(Error::InvalidCiphertext)
+            );
+        }
+
+        let alice = ecdsa::keypair_from_suri("//Alice").unwrap();
+        assert_eq!(
+            encrypt(&alice[32..], KeyType::Ecdsa, b"dots"),
+            Err(Error::UnsupportedKeyType)
+        );
+    }
+}
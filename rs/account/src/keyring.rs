@@ -0,0 +1,234 @@
+use std::collections::BTreeMap;
+
+use error::Error;
+use wasm_bindgen::prelude::*;
+use zeroize::Zeroizing;
+
+use crate::{
+    address_from_public_key, decrypt, ecdsa, ed25519, keypair_from_secret, keypair_from_suri,
+    secret_from_phrase, sign, wrap_bytes, KeyType,
+};
+
+/// A keypair that is held by a `Keyring`; the keypair is zeroized when the account is dropped
+struct Account {
+    key_type: KeyType,
+    keypair: Zeroizing<Vec<u8>>,
+}
+
+impl Account {
+    fn public_key(&self) -> &[u8] {
+        &self.keypair[self.key_type.key_lengths().0..]
+    }
+
+    fn private_key(&self) -> &[u8] {
+        &self.keypair[..self.key_type.key_lengths().0]
+    }
+}
+
+/// A collection of keypairs that are kept in Wasm memory
+///
+/// Accounts are referred to by opaque handles so that private keys never cross the JavaScript
+/// boundary unless they are explicitly exported.
+#[wasm_bindgen]
+#[derive(Default)]
+pub struct Keyring {
+    accounts: BTreeMap<u32, Account>,
+    next_handle: u32,
+}
+
+impl Keyring {
+    fn insert(&mut self, key_type: KeyType, keypair: Vec<u8>) -> u32 {
+        let handle = self.next_handle;
+        self.next_handle += 1;
+
+        self.accounts.insert(
+            handle,
+            Account {
+                key_type,
+                keypair: Zeroizing::new(keypair),
+            },
+        );
+
+        handle
+    }
+
+    fn account(&self, handle: u32) -> Result<&Account, Error> {
+        self.accounts.get(&handle).ok_or(Error::UnknownAccount)
+    }
+}
+
+#[wasm_bindgen]
+impl Keyring {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Keyring {
+        Keyring::default()
+    }
+
+    /// Add an account from a 32-byte secret
+    ///
+    /// Returns the handle of the new account
+    #[wasm_bindgen(js_name = addFromSecret)]
+    pub fn add_from_secret(&mut self, secret: &[u8], key_type: KeyType) -> Result<u32, Error> {
+        let keypair = match key_type {
+            KeyType::Sr25519 => keypair_from_secret(secret)?,
+            KeyType::Ed25519 => ed25519::keypair_from_secret(secret)?,
+            KeyType::Ecdsa => ecdsa::keypair_from_secret(secret)?,
+        };
+
+        Ok(self.insert(key_type, keypair))
+    }
+
+    /// Add an account from a bip39 phrase and password
+    ///
+    /// Returns the handle of the new account
+    #[wasm_bindgen(js_name = addFromPhrase)]
+    pub fn add_from_phrase(
+        &mut self,
+        phrase: &str,
+        password: &str,
+        key_type: KeyType,
+    ) -> Result<u32, Error> {
+        let secret = Zeroizing::new(secret_from_phrase(phrase, password)?);
+        self.add_from_secret(&secret, key_type)
+    }
+
+    /// Add an account from a secret URI (e.g. `//Alice` or `phrase//hard/soft///password`)
+    ///
+    /// Returns the handle of the new account
+    #[wasm_bindgen(js_name = addFromSuri)]
+    pub fn add_from_suri(&mut self, suri: &str, key_type: KeyType) -> Result<u32, Error> {
+        let keypair = match key_type {
+            KeyType::Sr25519 => keypair_from_suri(suri)?,
+            KeyType::Ed25519 => ed25519::keypair_from_suri(suri)?,
+            KeyType::Ecdsa => ecdsa::keypair_from_suri(suri)?,
+        };
+
+        Ok(self.insert(key_type, keypair))
+    }
+
+    /// Remove an account and zeroize its keypair
+    ///
+    /// Returns true if the keyring held an account with the handle
+    pub fn remove(&mut self, handle: u32) -> bool {
+        self.accounts.remove(&handle).is_some()
+    }
+
+    /// Returns the handles of the accounts in the keyring
+    pub fn handles(&self) -> Vec<u32> {
+        self.accounts.keys().copied().collect()
+    }
+
+    /// Returns the key type of an account
+    #[wasm_bindgen(js_name = keyType)]
+    pub fn key_type(&self, handle: u32) -> Result<KeyType, Error> {
+        Ok(self.account(handle)?.key_type)
+    }
+
+    /// Returns the public key of an account
+    #[wasm_bindgen(js_name = publicKey)]
+    pub fn public_key(&self, handle: u32) -> Result<Vec<u8>, Error> {
+        Ok(self.account(handle)?.public_key().to_vec())
+    }
+
+    /// Returns the Ss58 address of an account for the given Ss58 format
+    pub fn address(&self, handle: u32, format: u16) -> Result<String, Error> {
+        let account = self.account(handle)?;
+        match account.key_type {
+            KeyType::Ecdsa => ecdsa::address(account.public_key(), format),
+            _ => address_from_public_key(account.public_key(), format),
+        }
+    }
+
+    /// Sign a message with an account
+    ///
+    /// ECDSA accounts sign the blake2b-256 hash of the message, as expected by
+    /// `MultiSignature::Ecdsa`.
+    ///
+    /// Returns the signature
+    pub fn sign(&self, handle: u32, message: &[u8]) -> Result<Vec<u8>, Error> {
+        let account = self.account(handle)?;
+        match account.key_type {
+            KeyType::Sr25519 => sign(account.public_key(), account.private_key(), message),
+            KeyType::Ed25519 => ed25519::sign(account.private_key(), message),
+            KeyType::Ecdsa => {
+                ecdsa::sign(account.private_key(), message, ecdsa::Hasher::Blake2_256)
+            }
+        }
+    }
+
+    /// Sign a raw message with an account as wallet extensions do (i.e. wrapped in
+    /// `<Bytes>...</Bytes>`)
+    ///
+    /// Returns the signature
+    #[wasm_bindgen(js_name = signRaw)]
+    pub fn sign_raw(&self, handle: u32, message: &[u8]) -> Result<Vec<u8>, Error> {
+        self.sign(handle, &wrap_bytes(message))
+    }
+
+    /// Decrypt a message that was encrypted for an sr25519 or ed25519 account with `encrypt`
+    ///
+    /// Returns the decrypted message
+    pub fn decrypt(&self, handle: u32, encrypted: &[u8]) -> Result<Vec<u8>, Error> {
+        let account = self.account(handle)?;
+        decrypt(
+            account.public_key(),
+            account.private_key(),
+            account.key_type,
+            encrypted,
+        )
+    }
+
+    /// Export the keypair of an account
+    ///
+    /// Returns the keypair in the layout of the `keypairFrom*` function for the account's key type
+    #[wasm_bindgen(js_name = exportKeypair)]
+    pub fn export_keypair(&self, handle: u32) -> Result<Vec<u8>, Error> {
+        Ok(self.account(handle)?.keypair.to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use error::Error;
+
+    use crate::{ed25519, encrypt, keyring::Keyring, verify, KeyType, DEV_PHRASE};
+
+    #[test]
+    fn manages_accounts() {
+        let mut keyring = Keyring::new();
+
+        let alice = keyring.add_from_suri("//Alice", KeyType::Sr25519).unwrap();
+        let bob = keyring.add_from_suri("//Bob", KeyType::Ed25519).unwrap();
+        let dev = keyring
+            .add_from_phrase(DEV_PHRASE, "", KeyType::Ecdsa)
+            .unwrap();
+
+        assert_eq!(keyring.handles(), vec![alice, bob, dev]);
+        assert_eq!(
+            keyring.address(alice, 42).unwrap(),
+            "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"
+        );
+
+        let signature = keyring.sign(alice, b"dots").unwrap();
+        assert!(verify(
+            &keyring.public_key(alice).unwrap(),
+            b"dots",
+            &signature
+        ));
+
+        let signature = keyring.sign(bob, b"dots").unwrap();
+        assert!(ed25519::verify(
+            &keyring.public_key(bob).unwrap(),
+            b"dots",
+            &signature
+        ));
+
+        let encrypted = encrypt(&keyring.public_key(bob).unwrap(), KeyType::Ed25519, b"dots");
+        assert_eq!(keyring.decrypt(bob, &encrypted.unwrap()).unwrap(), b"dots");
+
+        assert!(keyring.remove(alice));
+        assert!(!keyring.remove(alice));
+        assert_eq!(keyring.sign(alice, b"dots"), Err(Error::UnknownAccount));
+        assert_eq!(keyring.handles(), vec![bob, dev]);
+    }
+}
@@ -0,0 +1,383 @@
+use crypto_secretbox::{
+    aead::{rand_core::RngCore, Aead, KeyInit, OsRng},
+    XSalsa20Poly1305,
+};
+use error::Error;
+use schnorrkel::SecretKey;
+use scrypt::Params;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use wasm_bindgen::prelude::*;
+
+use crate::{address_from_public_key, blake2_256, decode_address, ecdsa, ed25519, KeyType};
+
+const PKCS8_HEADER: [u8; 16] = [48, 83, 2, 1, 1, 48, 5, 6, 3, 43, 101, 112, 4, 34, 4, 32];
+const PKCS8_DIVIDER: [u8; 5] = [161, 35, 3, 33, 0];
+
+const SCRYPT_LOG_N: u8 = 15;
+const SCRYPT_P: u32 = 1;
+const SCRYPT_R: u32 = 8;
+
+/// A Polkadot-JS (version 3) JSON account
+///
+/// ref: https://github.com/polkadot-js/common/blob/v10.1.9/packages/keyring/src/pair/toJson.ts
+#[derive(Serialize, Deserialize)]
+struct Keystore {
+    encoded: String,
+    encoding: Encoding,
+    address: String,
+    meta: Value,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Encoding {
+    content: Vec<String>,
+    #[serde(rename = "type")]
+    kind: Vec<String>,
+    version: String,
+}
+
+impl KeyType {
+    fn name(&self) -> &'static str {
+        match self {
+            KeyType::Sr25519 => "sr25519",
+            KeyType::Ed25519 => "ed25519",
+            KeyType::Ecdsa => "ecdsa",
+        }
+    }
+}
+
+/// Derive the 32-byte encryption key for a password
+fn scrypt_key(password: &str, salt: &[u8], params: &Params) -> Result<[u8; 32], Error> {
+    let mut key = [0u8; 64];
+    scrypt::scrypt(password.as_bytes(), salt, params, &mut key)
+        .map_err(|_| Error::InvalidKeystore)?;
+
+    let mut res = [0u8; 32];
+    res.copy_from_slice(&key[..32]);
+    Ok(res)
+}
+
+/// Derive the public key of a secret key in the layout of the `keypairFrom*` functions
+fn public_from_secret(secret: &[u8], key_type: KeyType) -> Result<Vec<u8>, Error> {
+    match key_type {
+        KeyType::Sr25519 => SecretKey::from_ed25519_bytes(secret)
+            .map(|s| s.to_public().to_bytes().to_vec())
+            .map_err(|_| Error::InvalidKeystore),
+        KeyType::Ed25519 => ed25519::keypair_from_secret(secret).map(|k| k[32..].to_vec()),
+        KeyType::Ecdsa => ecdsa::keypair_from_secret(secret).map(|k| k[32..].to_vec()),
+    }
+    .map_err(|_| Error::InvalidKeystore)
+}
+
+/// Check that the `address` of a JSON account belongs to its public key
+fn check_address(address: &str, public: &[u8], content: &str) -> Result<(), Error> {
+    let matches = match content {
+        "ethereum" => ecdsa::ethereum_address(public).map(|expected| {
+            address
+                .strip_prefix("0x")
+                .is_some_and(|a| a.eq_ignore_ascii_case(&hex::encode(expected)))
+        }),
+        "ecdsa" => decode_address(address).map(|a| a.public_key() == blake2_256(public)),
+        _ => decode_address(address).map(|a| a.public_key() == public),
+    };
+
+    match matches {
+        Ok(true) => Ok(()),
+        _ => Err(Error::InvalidKeystore),
+    }
+}
+
+/// Encrypt a keypair as a Polkadot-JS JSON account
+///
+/// * keypair: keypair as returned by the `keypairFrom*` function for the key type
+/// * key_type: signature scheme of the keypair
+/// * password: password with which to encrypt the keypair
+/// * meta: JSON object of account metadata (e.g. `{"name":"Alice"}`)
+/// * format: Ss58 format of the account's address
+///
+/// * Returns the JSON account
+/// ref: https://github.com/polkadot-js/common/blob/v10.1.9/packages/keyring/src/pair/encode.ts
+#[wasm_bindgen(js_name = encryptKeystore)]
+pub fn encrypt_keystore(
+    keypair: &[u8],
+    key_type: KeyType,
+    password: &str,
+    meta: &str,
+    format: u16,
+) -> Result<String, Error> {
+    let (secret_len, public_len) = key_type.key_lengths();
+    if keypair.len() != secret_len + public_len {
+        return Err(Error::InvalidKey);
+    }
+
+    let meta: Value = match serde_json::from_str(meta) {
+        Ok(m @ Value::Object(_)) => m,
+        _ => return Err(Error::InvalidKeystore),
+    };
+
+    // `decryptKeystore` rejects accounts whose public key does not belong to the secret key
+    let (secret, public) = keypair.split_at(secret_len);
+    if public_from_secret(secret, key_type).map_err(|_| Error::InvalidKey)? != public {
+        return Err(Error::InvalidKey);
+    }
+
+    let address = match key_type {
+        KeyType::Ecdsa => ecdsa::address(public, format)?,
+        _ => address_from_public_key(public, format)?,
+    };
+
+    let mut plaintext = PKCS8_HEADER.to_vec();
+    plaintext.extend_from_slice(secret);
+    // Polkadot-JS represents ed25519 secret keys as the seed followed by the public key
+    if key_type == KeyType::Ed25519 {
+        plaintext.extend_from_slice(public);
+    }
+    plaintext.extend_from_slice(&PKCS8_DIVIDER);
+    plaintext.extend_from_slice(public);
+
+    let mut salt = [0u8; 32];
+    let mut nonce = [0u8; 24];
+    OsRng.fill_bytes(&mut salt);
+    OsRng.fill_bytes(&mut nonce);
+
+    let params =
+        Params::new(SCRYPT_LOG_N, SCRYPT_R, SCRYPT_P).map_err(|_| Error::InvalidKeystore)?;
+    let key = scrypt_key(password, &salt, &params)?;
+    let ciphertext = XSalsa20Poly1305::new(&key.into())
+        .encrypt(&nonce.into(), plaintext.as_slice())
+        .map_err(|_| Error::InvalidKeystore)?;
+
+    let mut encoded = salt.to_vec();
+    encoded.extend_from_slice(&(1u32 << SCRYPT_LOG_N).to_le_bytes());
+    encoded.extend_from_slice(&SCRYPT_P.to_le_bytes());
+    encoded.extend_from_slice(&SCRYPT_R.to_le_bytes());
+    encoded.extend_from_slice(&nonce);
+    encoded.extend_from_slice(&ciphertext);
+
+    let keystore = Keystore {
+        encoded: base64::encode(encoded),
+        encoding: Encoding {
+            content: vec!["pkcs8".into(), key_type.name().into()],
+            kind: vec!["scrypt".into(), "xsalsa20-poly1305".into()],
+            version: "3".into(),
+        },
+        address,
+        meta,
+    };
+
+    serde_json::to_string(&keystore).map_err(|_| Error::InvalidKeystore)
+}
+
+/// Decrypt a Polkadot-JS JSON account
+///
+/// * json: JSON account
+/// * password: password with which the keypair was encrypted
+///
+/// * Returns the keypair in the layout of the `keypairFrom*` function for the account's key type
+/// ref: https://github.com/polkadot-js/common/blob/v10.1.9/packages/keyring/src/pair/decode.ts
+#[wasm_bindgen(js_name = decryptKeystore)]
+pub fn decrypt_keystore(json: &str, password: &str) -> Result<Vec<u8>, Error> {
+    let keystore: Keystore = serde_json::from_str(json).map_err(|_| Error::InvalidKeystore)?;
+
+    let content = keystore
+        .encoding
+        .content
+        .get(1)
+        .map(String::as_str)
+        .unwrap_or_default();
+    let key_type = match content {
+        "sr25519" => KeyType::Sr25519,
+        "ed25519" => KeyType::Ed25519,
+        "ecdsa" | "ethereum" => KeyType::Ecdsa,
+        _ => return Err(Error::InvalidKeystore),
+    };
+
+    if keystore.encoding.version != "3" || !keystore.encoding.kind.iter().any(|k| k == "scrypt") {
+        return Err(Error::InvalidKeystore);
+    }
+
+    let encoded = base64::decode(&keystore.encoded).map_err(|_| Error::InvalidKeystore)?;
+    if encoded.len() < 68 {
+        return Err(Error::InvalidKeystore);
+    }
+
+    let (salt, rest) = encoded.split_at(32);
+    let (params, rest) = rest.split_at(12);
+    let (nonce, ciphertext) = rest.split_at(24);
+
+    let param =
+        |i: usize| u32::from_le_bytes([params[i], params[i + 1], params[i + 2], params[i + 3]]);
+    // like Polkadot-JS, only accept the parameters that it writes, since a crafted file could
+    // otherwise make scrypt allocate gigabytes of memory or run for hours
+    if (param(0), param(4), param(8)) != (1 << SCRYPT_LOG_N, SCRYPT_P, SCRYPT_R) {
+        return Err(Error::InvalidKeystore);
+    }
+
+    let params =
+        Params::new(SCRYPT_LOG_N, SCRYPT_R, SCRYPT_P).map_err(|_| Error::InvalidKeystore)?;
+    let key = scrypt_key(password, salt, &params)?;
+    let plaintext = XSalsa20Poly1305::new(&key.into())
+        .decrypt(nonce.into(), ciphertext)
+        .map_err(|_| Error::InvalidPassword)?;
+
+    if !plaintext.starts_with(&PKCS8_HEADER) {
+        return Err(Error::InvalidKeystore);
+    }
+
+    // the secret key is either 64 or 32 bytes, depending on the key type and the exporting wallet
+    let body = &plaintext[PKCS8_HEADER.len()..];
+    let (secret, public) = [64, 32]
+        .into_iter()
+        .find(|len| body.get(*len..*len + PKCS8_DIVIDER.len()) == Some(&PKCS8_DIVIDER[..]))
+        .map(|len| (&body[..len], &body[len + PKCS8_DIVIDER.len()..]))
+        .ok_or(Error::InvalidKeystore)?;
+
+    let (secret_len, public_len) = key_type.key_lengths();
+    if secret.len() < secret_len || public.len() < public_len {
+        return Err(Error::InvalidKeystore);
+    }
+
+    // the stored public key and address are not authenticated by the secret, so check both
+    let derived = public_from_secret(&secret[..secret_len], key_type)?;
+    if public[..public_len] != derived[..] {
+        return Err(Error::InvalidKeystore);
+    }
+    check_address(&keystore.address, &derived, content)?;
+
+    let mut keypair = secret[..secret_len].to_vec();
+    keypair.extend_from_slice(&derived);
+    Ok(keypair)
+}
+
+#[cfg(test)]
+mod tests {
+    use crypto_secretbox::{
+        aead::{Aead, KeyInit},
+        XSalsa20Poly1305,
+    };
+    use error::Error;
+    use scrypt::Params;
+
+    use super::{scrypt_key, SCRYPT_LOG_N, SCRYPT_P, SCRYPT_R};
+    use crate::{
+        address_from_public_key, ed25519, keypair_from_suri,
+        keystore::{decrypt_keystore, encrypt_keystore},
+        KeyType,
+    };
+
+    /// Decrypt a JSON account, change its plaintext and encrypt it again with the same password
+    fn tamper(json: &str, password: &str, change: impl FnOnce(&mut Vec<u8>)) -> String {
+        let mut keystore: serde_json::Value = serde_json::from_str(json).unwrap();
+        let mut encoded = base64::decode(keystore["encoded"].as_str().unwrap()).unwrap();
+
+        let params = Params::new(SCRYPT_LOG_N, SCRYPT_R, SCRYPT_P).unwrap();
+        let key = scrypt_key(password, &encoded[..32], &params).unwrap();
+        let cipher = XSalsa20Poly1305::new(&key.into());
+        let nonce: [u8; 24] = encoded[44..68].try_into().unwrap();
+
+        let mut plaintext = cipher.decrypt(&nonce.into(), &encoded[68..]).unwrap();
+        change(&mut plaintext);
+        encoded.truncate(68);
+        encoded.extend(cipher.encrypt(&nonce.into(), plaintext.as_slice()).unwrap());
+
+        keystore["encoded"] = base64::encode(encoded).into();
+        keystore.to_string()
+    }
+
+    // `//Alice` (sr25519) and `//Bob` (ed25519) in the Polkadot-JS keyring's JSON format, with
+    // the password `dots`; these were produced by a separate implementation of the keyring's
+    // `encode` and `toJson`, not by this crate
+    const ALICE_JSON: &str = r#"{"encoded":"qSPO2Bo67nbANBNlhGrlPbnkYQdzg5Vu4ACjvQexy4UAgAAAAQAAAAgAAABiCPGnqp+P+deIsyW03Q4haNrPOsoxlsoc4MSVjD9YZ0CaMtgbSXOZCIzYR3bF8lvz27tJ1rIbJWwdgzck3cUXXqIz/k5DqNFIFCdyL5WK9sZneEy6TazDHEqXgqtq6rxGOx+3KRJlRExYwcs818iK+HiwqZaOnprGPnm738xkPYLwdlGIjHTj2qk9lQs0USZ1QdC3whKNwISBJ4c5","encoding":{"content":["pkcs8","sr25519"],"type":["scrypt","xsalsa20-poly1305"],"version":"3"},"address":"5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY","meta":{"genesisHash":"","name":"Alice","whenCreated":1665964800000}}"#;
+    const BOB_JSON: &str = r#"{"encoded":"whnUAcKhBQOMz4nwl78+sG2SDNCR6b84Se4BOuEpkwgAgAAAAQAAAAgAAADeSP9qFG9XjxKhRlEGfxknlJrUAmWGfxmWFI5zAFYY+ChVIBNvsiiLs6L2EVy1f4NfNd3BqK4FfaifHvSgx383mrILyx5+ZvdABvyKE5np+7b3JbmBr5J+SdGLvjtk6OvyqnJA0wJTh3sq+vHvcffy3wtxRQPc16Q/ZjNnL8WS2Wq+JGTt+XED/uumYoOAv3fJnn2T7mmXaTV7ZGzs","encoding":{"content":["pkcs8","ed25519"],"type":["scrypt","xsalsa20-poly1305"],"version":"3"},"address":"5GoNkf6WdbxCFnPdAnYYQyCjAKPJgLNxXwPjwTh6DGg6gN3E","meta":{"genesisHash":"","name":"Bob","whenCreated":1665964800000}}"#;
+
+    #[test]
+    fn round_trips() {
+        let alice = keypair_from_suri("//Alice").unwrap();
+        let json =
+            encrypt_keystore(&alice, KeyType::Sr25519, "dots", r#"{"name":"Alice"}"#, 42).unwrap();
+
+        assert!(json.contains("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"));
+        assert_eq!(decrypt_keystore(&json, "dots"), Ok(alice));
+        assert_eq!(decrypt_keystore(&json, "tods"), Err(Error::InvalidPassword));
+
+        let bob = ed25519::keypair_from_suri("//Bob").unwrap();
+        let json = encrypt_keystore(&bob, KeyType::Ed25519, "", "{}", 0).unwrap();
+        assert_eq!(decrypt_keystore(&json, ""), Ok(bob));
+
+        // a keypair whose halves do not belong together could not be restored
+        let alice = keypair_from_suri("//Alice").unwrap();
+        let bob = keypair_from_suri("//Bob").unwrap();
+        let mismatched = [&alice[..64], &bob[64..]].concat();
+        assert_eq!(
+            encrypt_keystore(&mismatched, KeyType::Sr25519, "dots", "{}", 42),
+            Err(Error::InvalidKey)
+        );
+    }
+
+    #[test]
+    fn decrypts_polkadot_js_accounts() {
+        let alice = decrypt_keystore(ALICE_JSON, "dots").unwrap();
+        assert_eq!(
+            hex::encode(&alice[..64]),
+            "98319d4ff8a9508c4bb0cf0b5a78d760a0b2082c02775e6e82370816fedfff48\
+             925a225d97aa00682d6a59b95b18780c10d7032336e88f3442b42361f4a66011"
+        );
+        assert_eq!(alice, keypair_from_suri("//Alice").unwrap());
+        assert_eq!(
+            address_from_public_key(&alice[64..], 42).unwrap(),
+            "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"
+        );
+
+        // Polkadot-JS stores ed25519 secret keys as the 32-byte seed followed by the public key
+        let bob = decrypt_keystore(BOB_JSON, "dots").unwrap();
+        assert_eq!(
+            hex::encode(&bob[..32]),
+            "3b7b60af2abcd57ba401ab398f84f4ca54bd6b2140d2503fbcf3286535fe3ff1"
+        );
+        assert_eq!(bob, ed25519::keypair_from_suri("//Bob").unwrap());
+        assert_eq!(
+            address_from_public_key(&bob[32..], 42).unwrap(),
+            "5GoNkf6WdbxCFnPdAnYYQyCjAKPJgLNxXwPjwTh6DGg6gN3E"
+        );
+
+        assert_eq!(
+            decrypt_keystore(BOB_JSON, "tods"),
+            Err(Error::InvalidPassword)
+        );
+    }
+
+    #[test]
+    fn rejects_tampered_accounts() {
+        // the address must belong to the decrypted key
+        let json = ALICE_JSON.replace(
+            "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY",
+            "5GoNkf6WdbxCFnPdAnYYQyCjAKPJgLNxXwPjwTh6DGg6gN3E",
+        );
+        assert_eq!(decrypt_keystore(&json, "dots"), Err(Error::InvalidKeystore));
+
+        // as must the public key that follows the secret key
+        let json = tamper(ALICE_JSON, "dots", |_| ());
+        assert!(decrypt_keystore(&json, "dots").is_ok());
+        let bob = keypair_from_suri("//Bob").unwrap();
+        let json = tamper(ALICE_JSON, "dots", |plaintext| {
+            let len = plaintext.len();
+            plaintext[len - 32..].copy_from_slice(&bob[64..]);
+        });
+        assert_eq!(decrypt_keystore(&json, "dots"), Err(Error::InvalidKeystore));
+
+        // scrypt parameters other than those that Polkadot-JS writes are rejected before use
+        let keystore: serde_json::Value = serde_json::from_str(ALICE_JSON).unwrap();
+        for (offset, value) in [(32, 1u32 << 31), (36, u32::MAX), (40, 1)] {
+            let mut encoded = base64::decode(keystore["encoded"].as_str().unwrap()).unwrap();
+            encoded[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+
+            let mut tampered = keystore.clone();
+            tampered["encoded"] = base64::encode(encoded).into();
+            assert_eq!(
+                decrypt_keystore(&tampered.to_string(), "dots"),
+                Err(Error::InvalidKeystore)
+            );
+        }
+    }
+}
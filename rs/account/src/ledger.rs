@@ -0,0 +1,172 @@
+use error::Error;
+use hmac::{Hmac, Mac};
+use sha2::{Sha256, Sha512};
+use wasm_bindgen::prelude::*;
+
+use crate::{address_from_public_key, bip32, ed25519, seed_from_phrase, SeedMode};
+
+/// Extended private key: 32-byte left half, 32-byte right half and 32-byte chain code
+type ExtendedKey = [u8; 96];
+
+fn hmac_sha512(key: &[u8], data: &[&[u8]]) -> [u8; 64] {
+    let mut mac = Hmac::<Sha512>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    data.iter().for_each(|d| mac.update(d));
+    mac.finalize().into_bytes().into()
+}
+
+/// Create the bip32-ed25519 master key from a bip39 seed
+fn master_key(seed: &[u8]) -> ExtendedKey {
+    let mut mac = Hmac::<Sha256>::new_from_slice(b"ed25519 seed").expect("valid key length");
+    mac.update(&[1]);
+    mac.update(seed);
+    let chain_code = mac.finalize().into_bytes();
+
+    // re-hash until the third highest bit of the last byte is clear
+    let mut private = hmac_sha512(b"ed25519 seed", &[seed]);
+    while private[31] & 0b0010_0000 != 0 {
+        private = hmac_sha512(b"ed25519 seed", &[&private]);
+    }
+
+    private[0] &= 0b1111_1000;
+    private[31] &= 0b0111_1111;
+    private[31] |= 0b0100_0000;
+
+    let mut key = [0u8; 96];
+    key[..64].copy_from_slice(&private);
+    key[64..].copy_from_slice(&chain_code);
+    key
+}
+
+/// Add two little-endian integers modulo 2^256
+fn add_le(a: &[u8], b: &[u8; 32]) -> [u8; 32] {
+    let mut sum = [0u8; 32];
+    let mut carry = 0u16;
+    for i in 0..32 {
+        let s = a[i] as u16 + b[i] as u16 + carry;
+        sum[i] = s as u8;
+        carry = s >> 8;
+    }
+
+    sum
+}
+
+/// Derive a hardened child key
+fn derive_hardened(key: &ExtendedKey, index: u32) -> ExtendedKey {
+    let (kl, kr, chain_code) = (&key[..32], &key[32..64], &key[64..]);
+    let index = (index | bip32::HARDENED).to_le_bytes();
+
+    let z = hmac_sha512(chain_code, &[&[0], kl, kr, &index]);
+    let child_chain_code = hmac_sha512(chain_code, &[&[1], kl, kr, &index]);
+
+    // 8 * zl, where zl is the first 28 bytes of z
+    let mut zl = [0u8; 32];
+    for i in 0..29 {
+        let low = if i < 28 { z[i] << 3 } else { 0 };
+        let high = if i > 0 { z[i - 1] >> 5 } else { 0 };
+        zl[i] = low | high;
+    }
+
+    let mut zr = [0u8; 32];
+    zr.copy_from_slice(&z[32..]);
+
+    let mut child = [0u8; 96];
+    child[..32].copy_from_slice(&add_le(kl, &zl));
+    child[32..64].copy_from_slice(&add_le(kr, &zr));
+    child[64..].copy_from_slice(&child_chain_code[32..]);
+    child
+}
+
+/// Get the derivation path that Ledger apps use for an account
+///
+/// * slip44: coin type of the network (e.g. 354 for Polkadot or 434 for Kusama)
+/// * account: account index
+/// * address_index: address index
+///
+/// Returns a path of the form `m/44'/354'/0'/0'/0'`
+#[wasm_bindgen(js_name = ledgerPath)]
+pub fn path(slip44: u32, account: u32, address_index: u32) -> String {
+    format!("m/44'/{}'/{}'/0'/{}'", slip44, account, address_index)
+}
+
+/// Create the ed25519 keypair that a Ledger device derives from a bip39 phrase
+///
+/// * phrase: mnemonic phrase
+/// * password: bip39 password (i.e. the "25th word"), which is empty by default
+/// * path: bip32 derivation path (e.g. `m/44'/354'/0'/0'/0'`); Ledger apps only support hardened
+///   derivation, so every index in the path is hardened
+///
+/// Returns a 64-byte vector that is the result of appending the 32-byte public key to the end of the
+/// 32-byte private key
+///
+/// ref: https://github.com/polkadot-js/common/blob/master/packages/util-crypto/src/key/hdLedger.ts
+#[wasm_bindgen(js_name = ledgerKeypairFromPhrase)]
+pub fn keypair_from_phrase(phrase: &str, password: &str, path: &str) -> Result<Vec<u8>, Error> {
+    let seed = seed_from_phrase(phrase, password, SeedMode::Bip39)?;
+    let key = bip32::parse_path(path)?
+        .into_iter()
+        .fold(master_key(&seed), |key, index| derive_hardened(&key, index));
+
+    ed25519::keypair_from_secret(&key[..32])
+}
+
+/// Get the Ss58 address of the ed25519 account that a Ledger device derives from a bip39 phrase
+///
+/// * phrase: mnemonic phrase
+/// * password: bip39 password (i.e. the "25th word"), which is empty by default
+/// * path: bip32 derivation path (e.g. `m/44'/354'/0'/0'/0'`)
+/// * format: Ss58 format of the address
+///
+/// Returns the Ss58 address of the account
+#[wasm_bindgen(js_name = ledgerAddressFromPhrase)]
+pub fn address_from_phrase(
+    phrase: &str,
+    password: &str,
+    path: &str,
+    format: u16,
+) -> Result<String, Error> {
+    let keypair = keypair_from_phrase(phrase, password, path)?;
+    address_from_public_key(&keypair[32..], format)
+}
+
+#[cfg(test)]
+mod tests {
+    use error::Error;
+
+    use crate::{address_from_public_key, ed25519, ledger, DEV_PHRASE};
+
+    #[test]
+    fn derives_ledger_accounts() {
+        let path = ledger::path(354, 0, 0);
+        assert_eq!(path, "m/44'/354'/0'/0'/0'");
+
+        // computed independently with the algorithm of polkadot-js `hdLedger`
+        let keypair = ledger::keypair_from_phrase(DEV_PHRASE, "", &path).unwrap();
+        assert_eq!(
+            hex::encode(&keypair),
+            "b8ee5019cf9d34355cb10ca235ee142ceccd6d5182a499d4a7fb085e9f39705e\
+             7e94759318da12ef3678a2ccf851094eda08c738c164bf6d2729462db81100fe"
+        );
+
+        // every index is hardened
+        assert_eq!(
+            ledger::keypair_from_phrase(DEV_PHRASE, "", "m/44/354/0/0/0").unwrap(),
+            keypair
+        );
+        assert_ne!(
+            ledger::keypair_from_phrase(DEV_PHRASE, "password", &path).unwrap(),
+            keypair
+        );
+
+        let signature = ed25519::sign(&keypair[..32], b"dots").unwrap();
+        assert!(ed25519::verify(&keypair[32..], b"dots", &signature));
+
+        assert_eq!(
+            ledger::address_from_phrase(DEV_PHRASE, "", &path, 0).unwrap(),
+            address_from_public_key(&keypair[32..], 0).unwrap()
+        );
+        assert_eq!(
+            ledger::address_from_phrase(DEV_PHRASE, "", "44'/354'", 0),
+            Err(Error::InvalidPath)
+        );
+    }
+}
@@ -1,13 +1,48 @@
-use base58::ToBase58;
-use bip39::{Language, Mnemonic, MnemonicType};
-use blake2_rfc::blake2b::Blake2b;
+use bip39::{Language, Mnemonic, MnemonicType, Seed};
+use blake2_rfc::blake2b::blake2b;
+use error::Error;
 use hmac::Hmac;
 use pbkdf2::pbkdf2;
-use schnorrkel::{ExpansionMode, MiniSecretKey, PublicKey, SecretKey};
+use schnorrkel::{
+    derive::{ChainCode, Derivation},
+    signing_context, ExpansionMode, Keypair, MiniSecretKey, PublicKey, SecretKey, Signature,
+};
 use sha2::Sha512;
-use ss58_registry::Ss58AddressFormat;
 use wasm_bindgen::prelude::*;
 
+mod account_id;
+mod bip32;
+mod dev;
+pub mod ecdsa;
+pub mod ed25519;
+mod encryption;
+pub mod keyring;
+pub mod keystore;
+pub mod ledger;
+mod mnemonic;
+mod musig;
+mod session;
+mod shamir;
+mod ss58;
+mod suri;
+mod transcript;
+mod vanity;
+mod vrf;
+
+pub use account_id::*;
+pub use dev::*;
+pub use encryption::*;
+pub use mnemonic::*;
+pub use musig::*;
+pub use session::*;
+pub use shamir::*;
+pub use ss58::*;
+pub use suri::DEV_PHRASE;
+use suri::{parse_path, DeriveJunction, Suri};
+pub use transcript::*;
+pub use vanity::*;
+pub use vrf::*;
+
 #[wasm_bindgen(js_name = phraseSize)]
 pub enum PhraseSize {
     Words12,
@@ -17,62 +52,175 @@ pub enum PhraseSize {
     Words24,
 }
 
+/// The signature schemes that are supported for Substrate accounts
+#[wasm_bindgen(js_name = keyType)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeyType {
+    Sr25519,
+    Ed25519,
+    Ecdsa,
+}
+
+impl KeyType {
+    /// The lengths of the private and public keys in this crate's keypair layout
+    pub(crate) fn key_lengths(&self) -> (usize, usize) {
+        match self {
+            KeyType::Sr25519 => (64, 32),
+            KeyType::Ed25519 => (32, 32),
+            KeyType::Ecdsa => (32, 33),
+        }
+    }
+}
+
+#[wasm_bindgen(js_name = phraseLanguage)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PhraseLanguage {
+    English,
+    ChineseSimplified,
+    ChineseTraditional,
+    French,
+    Italian,
+    Japanese,
+    Korean,
+    Spanish,
+}
+
+impl PhraseLanguage {
+    /// The languages in the order in which they are tried when detecting the language of a phrase
+    const ALL: [PhraseLanguage; 8] = [
+        PhraseLanguage::English,
+        PhraseLanguage::ChineseSimplified,
+        PhraseLanguage::ChineseTraditional,
+        PhraseLanguage::French,
+        PhraseLanguage::Italian,
+        PhraseLanguage::Japanese,
+        PhraseLanguage::Korean,
+        PhraseLanguage::Spanish,
+    ];
+}
+
+impl From<PhraseLanguage> for Language {
+    fn from(language: PhraseLanguage) -> Self {
+        match language {
+            PhraseLanguage::English => Language::English,
+            PhraseLanguage::ChineseSimplified => Language::ChineseSimplified,
+            PhraseLanguage::ChineseTraditional => Language::ChineseTraditional,
+            PhraseLanguage::French => Language::French,
+            PhraseLanguage::Italian => Language::Italian,
+            PhraseLanguage::Japanese => Language::Japanese,
+            PhraseLanguage::Korean => Language::Korean,
+            PhraseLanguage::Spanish => Language::Spanish,
+        }
+    }
+}
+
+/// How a seed is derived from a bip39 phrase
+#[wasm_bindgen(js_name = seedMode)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SeedMode {
+    /// PBKDF2 over the entropy of the phrase, as used by Substrate and polkadot-js
+    Substrate,
+    /// PBKDF2 over the text of the phrase, as specified by bip39 and used by Ledger and most
+    /// other wallets
+    Bip39,
+}
+
 /// Create a new bip39 phrase
 ///
 /// size: number of words in the phrase
+/// language: language of the words in the phrase
 ///
 /// Returns a new bip39 phrase
 #[wasm_bindgen(js_name = newPhrase)]
-pub fn new_phrase(size: PhraseSize) -> String {
-    match size {
-        PhraseSize::Words12 => {
-            Mnemonic::new(MnemonicType::Words12, Language::English).into_phrase()
-        }
-        PhraseSize::Words15 => {
-            Mnemonic::new(MnemonicType::Words15, Language::English).into_phrase()
-        }
-        PhraseSize::Words18 => {
-            Mnemonic::new(MnemonicType::Words18, Language::English).into_phrase()
-        }
-        PhraseSize::Words21 => {
-            Mnemonic::new(MnemonicType::Words21, Language::English).into_phrase()
-        }
-        PhraseSize::Words24 => {
-            Mnemonic::new(MnemonicType::Words24, Language::English).into_phrase()
-        }
-    }
+pub fn new_phrase(size: PhraseSize, language: PhraseLanguage) -> String {
+    let mtype = match size {
+        PhraseSize::Words12 => MnemonicType::Words12,
+        PhraseSize::Words15 => MnemonicType::Words15,
+        PhraseSize::Words18 => MnemonicType::Words18,
+        PhraseSize::Words21 => MnemonicType::Words21,
+        PhraseSize::Words24 => MnemonicType::Words24,
+    };
+
+    Mnemonic::new(mtype, language.into()).into_phrase()
 }
 
-/// Create a secret from a bip39 phrase
+/// Detect the language of a bip39 phrase
 ///
 /// phrase: mnemonic phrase
-/// password: password for the secret
 ///
-/// Returns the 32-byte secret via entropy
+/// Returns the language of the phrase if it is a valid bip39 phrase
+#[wasm_bindgen(js_name = phraseLanguageOf)]
+pub fn phrase_language(phrase: &str) -> Result<PhraseLanguage, Error> {
+    PhraseLanguage::ALL
+        .into_iter()
+        .find(|language| Mnemonic::validate(phrase, (*language).into()).is_ok())
+        .ok_or(Error::InvalidPhrase)
+}
+
+/// Parse a bip39 phrase in any supported language
+fn mnemonic_from_phrase(phrase: &str) -> Result<Mnemonic, Error> {
+    Mnemonic::from_phrase(phrase, phrase_language(phrase)?.into()).map_err(|_| Error::InvalidPhrase)
+}
+
+/// Translate a bip39 phrase into another language
 ///
-/// ref: https://github.com/polkadot-js/wasm/blob/v6.3.1/packages/wasm-crypto/src/rs/bip39.rs#L39:L60
-#[wasm_bindgen(js_name = secretFromPhrase)]
-pub fn secret_from_phrase(phrase: &str, password: &str) -> Vec<u8> {
-    match Mnemonic::validate(phrase, Language::English) {
-        Ok(noop) => noop,
-        _ => panic!("Invalid phrase"),
+/// phrase: mnemonic phrase in any supported language
+/// language: language of the translated phrase
+///
+/// Returns the phrase for the same entropy in the given language
+#[wasm_bindgen(js_name = convertPhrase)]
+pub fn convert_phrase(phrase: &str, language: PhraseLanguage) -> Result<String, Error> {
+    let mnemonic = mnemonic_from_phrase(phrase)?;
+
+    match Mnemonic::from_entropy(mnemonic.entropy(), language.into()) {
+        Ok(m) => Ok(m.into_phrase()),
+        _ => Err(Error::InvalidPhrase),
     }
+}
+
+/// Create a seed from a bip39 phrase
+///
+/// phrase: mnemonic phrase in any supported language
+/// password: password for the seed
+/// mode: how the seed is derived from the phrase
+///
+/// Returns the 64-byte seed
+///
+/// ref: https://github.com/bitcoin/bips/blob/master/bip-0039.mediawiki
+#[wasm_bindgen(js_name = seedFromPhrase)]
+pub fn seed_from_phrase(phrase: &str, password: &str, mode: SeedMode) -> Result<Vec<u8>, Error> {
+    let mnemonic = mnemonic_from_phrase(phrase)?;
 
-    match Mnemonic::from_phrase(phrase, Language::English) {
-        Ok(m) => {
+    match mode {
+        SeedMode::Substrate => {
             let mut res = [0u8; 64];
-            let mut seed = vec![];
+            let mut salt = vec![];
 
-            seed.extend_from_slice(b"mnemonic");
-            seed.extend_from_slice(password.as_bytes());
+            salt.extend_from_slice(b"mnemonic");
+            salt.extend_from_slice(password.as_bytes());
 
-            pbkdf2::<Hmac<Sha512>>(m.entropy(), &seed, 2048, &mut res);
-            res[..32].to_vec()
+            pbkdf2::<Hmac<Sha512>>(mnemonic.entropy(), &salt, 2048, &mut res);
+            Ok(res.to_vec())
         }
-        _ => panic!("Invalid phrase"),
+        SeedMode::Bip39 => Ok(Seed::new(&mnemonic, password).as_bytes().to_vec()),
     }
 }
 
+/// Create a secret from a bip39 phrase
+///
+/// phrase: mnemonic phrase in any supported language
+/// password: password for the secret
+///
+/// Returns the 32-byte secret via entropy
+///
+/// ref: https://github.com/polkadot-js/wasm/blob/v6.3.1/packages/wasm-crypto/src/rs/bip39.rs#L39:L60
+#[wasm_bindgen(js_name = secretFromPhrase)]
+pub fn secret_from_phrase(phrase: &str, password: &str) -> Result<Vec<u8>, Error> {
+    let mut seed = seed_from_phrase(phrase, password, SeedMode::Substrate)?;
+    seed.truncate(32);
+    Ok(seed)
+}
+
 /// Create an sr25519 keypair from a secret
 ///
 /// secret: 32-byte secret
@@ -82,56 +230,88 @@ pub fn secret_from_phrase(phrase: &str, password: &str) -> Vec<u8> {
 ///
 /// ref: https://github.com/polkadot-js/wasm/blob/v6.3.1/packages/wasm-crypto/src/rs/sr25519.rs#L81:L96
 #[wasm_bindgen(js_name = keypairFromSecret)]
-pub fn keypair_from_secret(secret: &[u8]) -> Vec<u8> {
+pub fn keypair_from_secret(secret: &[u8]) -> Result<Vec<u8>, Error> {
     match MiniSecretKey::from_bytes(secret) {
-        Ok(s) => s
+        Ok(s) => Ok(s
             .expand_to_keypair(ExpansionMode::Ed25519)
             .to_half_ed25519_bytes()
-            .to_vec(),
-        _ => panic!("Invalid secret"),
+            .to_vec()),
+        _ => Err(Error::InvalidSecret),
     }
 }
 
-/// Create an Ss58 address from a public key and an Ss58 format
+/// Create an sr25519 keypair from a secret URI
 ///
-/// public_key: 32-byte public key
-/// format: Ss58 format
+/// suri: secret URI of the form `phrase//hard/soft///password`, where the phrase may also be a
+/// 0x-prefixed, 32-byte hex secret and defaults to the development phrase if omitted (e.g. `//Alice`)
 ///
-/// Returns the Ss58 address for the public key and format
+/// Returns a 96-byte vector that is the result of appending the 32-byte public key to the end of the
+/// 64-byte private key of the derived keypair
 ///
-/// ref: https://github.com/paritytech/substrate/blob/monthly-2022-09/primitives/core/src/crypto.rs#L317:L338
-#[wasm_bindgen(js_name = addressFromPublicKey)]
-pub fn address_from_public_key(public_key: &[u8], format: u8) -> String {
-    let network = match Ss58AddressFormat::try_from(format) {
-        Ok(network) => network,
-        _ => panic!("Unsupported network"),
+/// ref: https://github.com/paritytech/substrate/blob/monthly-2022-09/primitives/core/src/sr25519.rs
+#[wasm_bindgen(js_name = keypairFromSuri)]
+pub fn keypair_from_suri(suri: &str) -> Result<Vec<u8>, Error> {
+    let suri = Suri::parse(suri)?;
+    let keypair = match MiniSecretKey::from_bytes(&suri.secret()?) {
+        Ok(s) => s.expand_to_keypair(ExpansionMode::Ed25519),
+        _ => return Err(Error::InvalidSecret),
     };
 
-    let prefix = u16::from(network);
-    let mut bytes = match prefix {
-        0..=63 => vec![prefix as u8],
-        64..=16_383 => {
-            let first = ((prefix & 0b0000_0000_1111_1100) as u8) >> 2;
-            let second = ((prefix >> 8) as u8) | ((prefix & 0b0000_0000_0000_0011) as u8) << 6;
-
-            vec![first | 0b01000000, second]
-        }
-        _ => panic!("Unsupported network"),
-    };
+    Ok(derive_sr25519(keypair, &suri.path)
+        .to_half_ed25519_bytes()
+        .to_vec())
+}
 
-    bytes.extend(public_key);
+/// Derive an sr25519 public key without the corresponding private key
+///
+/// public_key: 32-byte public key
+/// path: derivation path of the form `/soft/soft`; public keys only support soft junctions
+///
+/// Returns the 32-byte public key of the account that `keypairFromSuri` derives for the same path
+///
+/// ref: https://github.com/paritytech/substrate/blob/monthly-2022-09/primitives/core/src/sr25519.rs
+#[wasm_bindgen(js_name = derivePublic)]
+pub fn derive_public(public_key: &[u8], path: &str) -> Result<Vec<u8>, Error> {
+    let public = PublicKey::from_bytes(public_key).map_err(|_| Error::InvalidKey)?;
 
-    let blake2b = {
-        let mut context = Blake2b::new(64);
-        context.update(b"SS58PRE");
-        context.update(&bytes);
+    parse_path(path)
+        .ok_or(Error::InvalidPath)?
+        .iter()
+        .try_fold(public, |public, junction| match junction {
+            DeriveJunction::Soft(cc) => Ok(public.derived_key_simple(ChainCode(*cc), []).0),
+            DeriveJunction::Hard(_) => Err(Error::UnsupportedDerivation),
+        })
+        .map(|public| public.to_bytes().to_vec())
+}
 
-        context.finalize()
-    };
+/// Derive the Ss58 address of an sr25519 account without the corresponding private key
+///
+/// * public_key: 32-byte public key
+/// * path: derivation path of the form `/soft/soft`
+/// * format: Ss58 format of the address
+///
+/// Returns the Ss58 address of the derived public key
+#[wasm_bindgen(js_name = deriveAddress)]
+pub fn derive_address(public_key: &[u8], path: &str, format: u16) -> Result<String, Error> {
+    address_from_public_key(&derive_public(public_key, path)?, format)
+}
 
-    bytes.extend(&blake2b.as_bytes()[0..2]);
+/// Apply a derivation path to an sr25519 keypair using Substrate's chain-code rules
+fn derive_sr25519(keypair: Keypair, path: &[DeriveJunction]) -> Keypair {
+    path.iter().fold(keypair, |pair, junction| match junction {
+        DeriveJunction::Soft(cc) => pair.derived_key_simple(ChainCode(*cc), []).0,
+        DeriveJunction::Hard(cc) => pair
+            .hard_derive_mini_secret_key(Some(ChainCode(*cc)), b"")
+            .0
+            .expand_to_keypair(ExpansionMode::Ed25519),
+    })
+}
 
-    bytes.to_base58()
+/// Hash data with blake2b-256
+pub(crate) fn blake2_256(data: &[u8]) -> [u8; 32] {
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(blake2b(32, &[], data).as_bytes());
+    hash
 }
 
 /// Sign a message
@@ -143,12 +323,309 @@ pub fn address_from_public_key(public_key: &[u8], format: u8) -> String {
 /// * Returns a 64-byte signature
 /// ref: https://github.com/polkadot-js/wasm/blob/v6.3.1/packages/wasm-crypto/src/rs/sr25519.rs#L113-#L132
 #[wasm_bindgen]
-pub fn sign(pubkey: &[u8], privkey: &[u8], message: &[u8]) -> Vec<u8> {
+pub fn sign(pubkey: &[u8], privkey: &[u8], message: &[u8]) -> Result<Vec<u8>, Error> {
+    sign_with_context(pubkey, privkey, b"substrate", message)
+}
+
+/// Verify a signature
+///
+/// * pubkey: 32-byte public key
+/// * message: message that was signed
+/// * signature: 64-byte signature
+///
+/// * Returns true if the signature is valid for the message and public key
+/// ref: https://github.com/polkadot-js/wasm/blob/v6.3.1/packages/wasm-crypto/src/rs/sr25519.rs
+#[wasm_bindgen]
+pub fn verify(pubkey: &[u8], message: &[u8], signature: &[u8]) -> bool {
+    verify_with_context(pubkey, b"substrate", message, signature)
+}
+
+/// Sign a message with a signing context other than `substrate`
+///
+/// * pubkey: 32-byte public key
+/// * privkey: 64-byte private key
+/// * context: signing context (e.g. `substrate`)
+/// * message: message to be signed
+///
+/// * Returns a 64-byte signature
+#[wasm_bindgen(js_name = signWithContext)]
+pub fn sign_with_context(
+    pubkey: &[u8],
+    privkey: &[u8],
+    context: &[u8],
+    message: &[u8],
+) -> Result<Vec<u8>, Error> {
     match (
         SecretKey::from_ed25519_bytes(privkey),
         PublicKey::from_bytes(pubkey),
     ) {
-        (Ok(s), Ok(k)) => s.sign_simple(b"substrate", message, &k).to_bytes().to_vec(),
-        _ => panic!("Invalid key"),
+        (Ok(s), Ok(k)) => Ok(s.sign_simple(context, message, &k).to_bytes().to_vec()),
+        _ => Err(Error::InvalidKey),
+    }
+}
+
+/// Verify a signature that was created with a signing context other than `substrate`
+///
+/// * pubkey: 32-byte public key
+/// * context: signing context that was used to sign the message
+/// * message: message that was signed
+/// * signature: 64-byte signature
+///
+/// * Returns true if the signature is valid for the context, message and public key
+#[wasm_bindgen(js_name = verifyWithContext)]
+pub fn verify_with_context(
+    pubkey: &[u8],
+    context: &[u8],
+    message: &[u8],
+    signature: &[u8],
+) -> bool {
+    match (
+        PublicKey::from_bytes(pubkey),
+        Signature::from_bytes(signature),
+    ) {
+        (Ok(k), Ok(s)) => k.verify_simple(context, message, &s).is_ok(),
+        _ => false,
+    }
+}
+
+const WRAP_PREFIX: &[u8] = b"<Bytes>";
+const WRAP_POSTFIX: &[u8] = b"</Bytes>";
+
+/// Wrap a message in `<Bytes>...</Bytes>` unless it is already wrapped
+///
+/// ref: https://github.com/polkadot-js/common/blob/v10.1.9/packages/util/src/u8a/wrap.ts
+pub(crate) fn wrap_bytes(message: &[u8]) -> Vec<u8> {
+    if message.starts_with(WRAP_PREFIX)
+        && message.ends_with(WRAP_POSTFIX)
+        && message.len() >= WRAP_PREFIX.len() + WRAP_POSTFIX.len()
+    {
+        return message.to_vec();
+    }
+
+    [WRAP_PREFIX, message, WRAP_POSTFIX].concat()
+}
+
+/// Sign a raw message as wallet extensions do (i.e. wrapped in `<Bytes>...</Bytes>`)
+///
+/// * pubkey: 32-byte public key
+/// * privkey: 64-byte private key
+/// * message: message to be wrapped and signed; messages that are already wrapped are not
+///   wrapped again
+///
+/// * Returns a 64-byte signature
+#[wasm_bindgen(js_name = signRaw)]
+pub fn sign_raw(pubkey: &[u8], privkey: &[u8], message: &[u8]) -> Result<Vec<u8>, Error> {
+    sign(pubkey, privkey, &wrap_bytes(message))
+}
+
+/// Verify a signature that was created by a wallet extension's raw signer
+///
+/// * pubkey: 32-byte public key
+/// * message: message that was signed, with or without its `<Bytes>...</Bytes>` wrapping
+/// * signature: 64-byte signature
+///
+/// * Returns true if the signature is valid for the wrapped message and public key
+#[wasm_bindgen(js_name = verifyRaw)]
+pub fn verify_raw(pubkey: &[u8], message: &[u8], signature: &[u8]) -> bool {
+    verify(pubkey, &wrap_bytes(message), signature)
+}
+
+/// Verify a batch of signatures
+///
+/// * messages: concatenation of the messages that were signed
+/// * message_lengths: length of each message in the concatenation
+/// * signatures: concatenation of the 64-byte signatures, one per message
+/// * pubkeys: concatenation of the 32-byte public keys, one per message
+///
+/// * Returns true if every signature is valid for its message and public key
+#[wasm_bindgen(js_name = verifyBatch)]
+pub fn verify_batch(
+    messages: &[u8],
+    message_lengths: &[u32],
+    signatures: &[u8],
+    pubkeys: &[u8],
+) -> bool {
+    // the lengths come from JavaScript, so their sum may overflow a 32-bit `usize`
+    let total = message_lengths
+        .iter()
+        .try_fold(0usize, |total, l| total.checked_add(*l as usize));
+
+    let count = message_lengths.len();
+    if count.checked_mul(64) != Some(signatures.len())
+        || count.checked_mul(32) != Some(pubkeys.len())
+        || total != Some(messages.len())
+    {
+        return false;
+    }
+
+    let (signatures, pubkeys) = match (
+        signatures
+            .chunks(64)
+            .map(Signature::from_bytes)
+            .collect::<Result<Vec<_>, _>>(),
+        pubkeys
+            .chunks(32)
+            .map(PublicKey::from_bytes)
+            .collect::<Result<Vec<_>, _>>(),
+    ) {
+        (Ok(s), Ok(k)) => (s, k),
+        _ => return false,
+    };
+
+    let mut offset = 0;
+    let transcripts = message_lengths.iter().map(|len| {
+        let message = &messages[offset..offset + *len as usize];
+        offset += *len as usize;
+        signing_context(b"substrate").bytes(message)
+    });
+
+    schnorrkel::verify_batch(transcripts, &signatures, &pubkeys, false).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use error::Error;
+
+    use crate::{
+        address_from_public_key, convert_phrase, derive_address, derive_public,
+        keypair_from_secret, keypair_from_suri, phrase_language, secret_from_phrase,
+        seed_from_phrase, sign, sign_raw, verify, verify_batch, verify_raw, PhraseLanguage,
+        SeedMode, DEV_PHRASE,
+    };
+
+    #[test]
+    fn derives_alice() {
+        let alice = keypair_from_suri("//Alice").unwrap();
+        let secret =
+            hex::decode("e5be9a5092b81bca64be81d212e7f2f9eba183bb7a90954f7b76361f6edb5c0a")
+                .unwrap();
+
+        assert_eq!(alice, keypair_from_secret(&secret).unwrap());
+        assert_eq!(
+            address_from_public_key(&alice[64..], 42).unwrap(),
+            "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"
+        );
+    }
+
+    #[test]
+    fn translates_phrases() {
+        let japanese = convert_phrase(DEV_PHRASE, PhraseLanguage::Japanese).unwrap();
+        assert_eq!(phrase_language(&japanese), Ok(PhraseLanguage::Japanese));
+        assert_eq!(
+            secret_from_phrase(&japanese, ""),
+            secret_from_phrase(DEV_PHRASE, "")
+        );
+
+        let french = convert_phrase(&japanese, PhraseLanguage::French).unwrap();
+        assert_eq!(phrase_language(&french), Ok(PhraseLanguage::French));
+        assert_eq!(
+            convert_phrase(&french, PhraseLanguage::English).unwrap(),
+            DEV_PHRASE
+        );
+    }
+
+    #[test]
+    fn derives_seeds() {
+        // root account of the development phrase (`subkey inspect "bottom drive ... walk"`)
+        let seed = seed_from_phrase(DEV_PHRASE, "", SeedMode::Substrate).unwrap();
+        assert_eq!(seed[..32], secret_from_phrase(DEV_PHRASE, "").unwrap());
+        assert_eq!(
+            address_from_public_key(&keypair_from_secret(&seed[..32]).unwrap()[64..], 42).unwrap(),
+            "5DfhGyQdFobKM8NsWvEeAKk5EQQgYe9AydgJ7rMB6E1EqRzV"
+        );
+
+        // first Trezor vector from the bip39 reference implementation
+        let phrase =
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon \
+                      abandon about";
+        assert_eq!(
+            hex::encode(seed_from_phrase(phrase, "TREZOR", SeedMode::Bip39).unwrap()),
+            "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04"
+        );
+    }
+
+    #[test]
+    fn derives_public_keys() {
+        let alice = keypair_from_suri("//Alice").unwrap();
+        let derived = keypair_from_suri("//Alice/deposit/1").unwrap();
+
+        assert_eq!(
+            derive_public(&alice[64..], "/deposit/1").unwrap(),
+            derived[64..]
+        );
+        assert_eq!(
+            derive_address(&alice[64..], "/deposit/1", 42).unwrap(),
+            address_from_public_key(&derived[64..], 42).unwrap()
+        );
+        assert_eq!(derive_public(&alice[64..], "").unwrap(), alice[64..]);
+        assert_eq!(
+            derive_public(&alice[64..], "/deposit//1"),
+            Err(Error::UnsupportedDerivation)
+        );
+        assert_eq!(
+            derive_public(&alice[64..], "deposit"),
+            Err(Error::InvalidPath)
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_input() {
+        assert_eq!(
+            secret_from_phrase("bottom drive obey lake", ""),
+            Err(Error::InvalidPhrase)
+        );
+        assert_eq!(keypair_from_secret(&[0u8; 16]), Err(Error::InvalidSecret));
+        assert_eq!(keypair_from_suri("//Alice//"), Err(Error::InvalidSecretUri));
+        assert_eq!(
+            sign(&[0u8; 32], &[0u8; 32], b"dots"),
+            Err(Error::InvalidKey)
+        );
+    }
+
+    #[test]
+    fn verifies_signatures() {
+        let alice = keypair_from_suri("//Alice").unwrap();
+        let bob = keypair_from_suri("//Bob").unwrap();
+
+        let first = sign(&alice[64..], &alice[..64], b"dots").unwrap();
+        let second = sign(&bob[64..], &bob[..64], b"substrate").unwrap();
+
+        assert!(verify(&alice[64..], b"dots", &first));
+        assert!(!verify(&bob[64..], b"dots", &first));
+
+        let pubkeys = [&alice[64..], &bob[64..]].concat();
+        assert!(verify_batch(
+            b"dotssubstrate",
+            &[4, 9],
+            &[first.clone(), second.clone()].concat(),
+            &pubkeys
+        ));
+        assert!(!verify_batch(
+            b"dotssubstrate",
+            &[4, 9],
+            &[second.clone(), first.clone()].concat(),
+            &pubkeys
+        ));
+        assert!(!verify_batch(
+            b"",
+            &[u32::MAX, 1],
+            &[first, second].concat(),
+            &pubkeys
+        ));
+    }
+
+    #[test]
+    fn verifies_raw_signatures() {
+        let alice = keypair_from_suri("//Alice").unwrap();
+        let signature = sign_raw(&alice[64..], &alice[..64], b"login").unwrap();
+
+        assert!(verify_raw(&alice[64..], b"login", &signature));
+        assert!(verify_raw(
+            &alice[64..],
+            b"<Bytes>login</Bytes>",
+            &signature
+        ));
+        assert!(verify(&alice[64..], b"<Bytes>login</Bytes>", &signature));
+        assert!(!verify(&alice[64..], b"login", &signature));
     }
 }
@@ -0,0 +1,196 @@
+use bip39::{Language, Mnemonic};
+use error::Error;
+use wasm_bindgen::prelude::*;
+
+use crate::{mnemonic_from_phrase, PhraseLanguage};
+
+/// Numbers of words that a bip39 phrase may have
+const WORD_COUNTS: [usize; 5] = [12, 15, 18, 21, 24];
+
+/// Check that each word is in the wordlist of a language
+fn check_words(words: &[&str], language: Language) -> Result<(), Error> {
+    let wordmap = language.wordmap();
+    match words.iter().position(|w| wordmap.get_bits(w).is_err()) {
+        Some(index) => Err(Error::InvalidWord(index + 1)),
+        None => Ok(()),
+    }
+}
+
+/// Get the entropy of a bip39 phrase
+///
+/// phrase: mnemonic phrase in any supported language
+///
+/// Returns the 16- to 32-byte entropy that is encoded by the phrase
+#[wasm_bindgen(js_name = entropyFromPhrase)]
+pub fn entropy_from_phrase(phrase: &str) -> Result<Vec<u8>, Error> {
+    Ok(mnemonic_from_phrase(phrase)?.entropy().to_vec())
+}
+
+/// Create the bip39 phrase that encodes some entropy
+///
+/// entropy: 16, 20, 24, 28 or 32 bytes of entropy
+/// language: language of the words in the phrase
+///
+/// Returns a 12- to 24-word phrase
+#[wasm_bindgen(js_name = phraseFromEntropy)]
+pub fn phrase_from_entropy(entropy: &[u8], language: PhraseLanguage) -> Result<String, Error> {
+    match Mnemonic::from_entropy(entropy, language.into()) {
+        Ok(m) => Ok(m.into_phrase()),
+        _ => Err(Error::InvalidEntropy),
+    }
+}
+
+/// Find the words in a wordlist that start with a prefix
+///
+/// prefix: beginning of a word
+/// language: language of the wordlist
+///
+/// Returns the matching words, separated by spaces
+#[wasm_bindgen(js_name = completeWord)]
+pub fn complete_word(prefix: &str, language: PhraseLanguage) -> String {
+    Language::from(language)
+        .wordlist()
+        .get_words_by_prefix(prefix)
+        .join(" ")
+}
+
+/// Validate a bip39 phrase
+///
+/// phrase: mnemonic phrase
+/// language: language of the phrase
+///
+/// Returns an error that identifies the first word that is not in the wordlist, an invalid number
+/// of words or an invalid checksum, in that order
+#[wasm_bindgen(js_name = validatePhrase)]
+pub fn validate_phrase(phrase: &str, language: PhraseLanguage) -> Result<(), Error> {
+    let words: Vec<&str> = phrase.split_whitespace().collect();
+    check_words(&words, language.into())?;
+
+    if !WORD_COUNTS.contains(&words.len()) {
+        return Err(Error::InvalidWordCount);
+    }
+
+    Mnemonic::validate(&words.join(" "), language.into()).map_err(|_| Error::InvalidPhraseChecksum)
+}
+
+/// Find the words that complete a bip39 phrase whose last word is missing
+///
+/// Only some of the words in the wordlist produce a valid checksum, so this can be used to recover
+/// a phrase whose last word was lost or to generate the last word of a hand-picked phrase
+///
+/// phrase: the first 11, 14, 17, 20 or 23 words of a mnemonic phrase
+/// language: language of the phrase
+///
+/// Returns the words that produce a valid phrase, separated by spaces
+#[wasm_bindgen(js_name = finalWords)]
+pub fn final_words(phrase: &str, language: PhraseLanguage) -> Result<String, Error> {
+    let words: Vec<&str> = phrase.split_whitespace().collect();
+    check_words(&words, language.into())?;
+
+    if !WORD_COUNTS.contains(&(words.len() + 1)) {
+        return Err(Error::InvalidWordCount);
+    }
+
+    let phrase = words.join(" ");
+    let candidates: Vec<&str> = Language::from(language)
+        .wordlist()
+        .get_words_by_prefix("")
+        .iter()
+        .filter(|w| Mnemonic::validate(&format!("{} {}", phrase, w), language.into()).is_ok())
+        .copied()
+        .collect();
+
+    Ok(candidates.join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use error::Error;
+
+    use crate::{
+        complete_word, entropy_from_phrase, final_words, phrase_from_entropy, validate_phrase,
+        PhraseLanguage, DEV_PHRASE,
+    };
+
+    #[test]
+    fn converts_entropy() {
+        // test vectors from the bip39 reference implementation
+        let vectors = [
+            (
+                "00000000000000000000000000000000",
+                "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon \
+                 abandon about",
+            ),
+            (
+                "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+                "legal winner thank year wave sausage worth useful legal winner thank yellow",
+            ),
+        ];
+
+        for (entropy, phrase) in vectors {
+            let entropy = hex::decode(entropy).unwrap();
+            assert_eq!(entropy_from_phrase(phrase).unwrap(), entropy);
+            assert_eq!(
+                phrase_from_entropy(&entropy, PhraseLanguage::English).unwrap(),
+                phrase
+            );
+        }
+
+        assert_eq!(
+            phrase_from_entropy(&[0u8; 17], PhraseLanguage::English),
+            Err(Error::InvalidEntropy)
+        );
+    }
+
+    #[test]
+    fn completes_words() {
+        assert_eq!(complete_word("aban", PhraseLanguage::English), "abandon");
+        assert_eq!(
+            complete_word("abs", PhraseLanguage::English),
+            "absent absorb abstract absurd"
+        );
+        assert_eq!(complete_word("xyz", PhraseLanguage::English), "");
+    }
+
+    #[test]
+    fn validates_phrases() {
+        assert_eq!(validate_phrase(DEV_PHRASE, PhraseLanguage::English), Ok(()));
+        assert_eq!(
+            validate_phrase(
+                &DEV_PHRASE.replace("lake", "lakes"),
+                PhraseLanguage::English
+            ),
+            Err(Error::InvalidWord(4))
+        );
+        assert_eq!(
+            validate_phrase("bottom drive obey lake", PhraseLanguage::English),
+            Err(Error::InvalidWordCount)
+        );
+        assert_eq!(
+            validate_phrase(
+                &DEV_PHRASE.replace("walk", "bottom"),
+                PhraseLanguage::English
+            ),
+            Err(Error::InvalidPhraseChecksum)
+        );
+        assert_eq!(
+            Error::InvalidWord(4).to_string(),
+            "Invalid word at position 4"
+        );
+    }
+
+    #[test]
+    fn repairs_phrases() {
+        let partial = DEV_PHRASE.strip_suffix(" walk").unwrap();
+        let words = final_words(partial, PhraseLanguage::English).unwrap();
+
+        // 12-word phrases have a 4-bit checksum, leaving 7 bits of entropy in the last word
+        assert_eq!(words.split(' ').count(), 128);
+        assert!(words.split(' ').any(|w| w == "walk"));
+
+        assert_eq!(
+            final_words(DEV_PHRASE, PhraseLanguage::English),
+            Err(Error::InvalidWordCount)
+        );
+    }
+}
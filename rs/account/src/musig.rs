@@ -0,0 +1,260 @@
+use std::mem;
+
+use error::Error;
+use merlin::Transcript;
+use schnorrkel::{
+    musig::{
+        aggregate_public_key_from_slice, AggregatePublicKey, CommitStage, Commitment, CosignStage,
+        Cosignature, MuSig, Reveal, RevealStage,
+    },
+    signing_context, Keypair, PublicKey, SecretKey,
+};
+use wasm_bindgen::prelude::*;
+
+enum Stage {
+    Commit(MuSig<Transcript, CommitStage<Keypair>>),
+    Reveal(MuSig<Transcript, RevealStage<Keypair>>),
+    Cosign(MuSig<Transcript, CosignStage>),
+    /// Held only while `reveal` or `cosign` moves the session to the next stage
+    Transition,
+}
+
+fn public_key(bytes: &[u8]) -> Result<PublicKey, Error> {
+    PublicKey::from_bytes(bytes).map_err(|_| Error::InvalidKey)
+}
+
+/// Compute the aggregate public key of a set of MuSig cosigners
+///
+/// public_keys: concatenated 32-byte public keys of the cosigners, in any order
+///
+/// Returns the 32-byte aggregate public key, which verifies the signatures that are created by
+/// `MuSigSession`
+#[wasm_bindgen(js_name = musigPublicKey)]
+pub fn musig_public_key(public_keys: &[u8]) -> Result<Vec<u8>, Error> {
+    // `usize::is_multiple_of` needs Rust 1.87
+    #[allow(clippy::manual_is_multiple_of)]
+    if public_keys.is_empty() || public_keys.len() % 32 != 0 {
+        return Err(Error::InvalidKey);
+    }
+
+    let mut keys = public_keys
+        .chunks(32)
+        .map(public_key)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(aggregate_public_key_from_slice(&mut keys)
+        .ok_or(Error::InvalidKey)?
+        .public_key()
+        .to_bytes()
+        .to_vec())
+}
+
+/// One cosigner's view of a MuSig signing session
+///
+/// Each cosigner creates a session for the same message and then, in three rounds, shares its
+/// commitment, reveal and cosignature with every other cosigner. The cosignatures are aggregated
+/// into a 64-byte sr25519 signature that `verify` accepts for the aggregate public key.
+///
+/// ref: https://github.com/w3f/schnorrkel/blob/master/src/musig.rs
+#[wasm_bindgen]
+pub struct MuSigSession {
+    stage: Stage,
+}
+
+#[wasm_bindgen]
+impl MuSigSession {
+    /// Start a session
+    ///
+    /// * pubkey: 32-byte public key of this cosigner
+    /// * privkey: 64-byte private key of this cosigner
+    /// * message: message to be signed
+    #[wasm_bindgen(constructor)]
+    pub fn new(pubkey: &[u8], privkey: &[u8], message: &[u8]) -> Result<MuSigSession, Error> {
+        let keypair = Keypair {
+            secret: SecretKey::from_ed25519_bytes(privkey).map_err(|_| Error::InvalidKey)?,
+            public: public_key(pubkey)?,
+        };
+        let transcript = signing_context(b"substrate").bytes(message);
+
+        Ok(MuSigSession {
+            stage: Stage::Commit(MuSig::new(keypair, transcript)),
+        })
+    }
+
+    /// Aggregate public key of the cosigners that have been added to the session
+    #[wasm_bindgen(getter, js_name = publicKey)]
+    pub fn public_key(&self) -> Result<Vec<u8>, Error> {
+        let key = match &self.stage {
+            Stage::Commit(m) => m.expected_public_key(),
+            Stage::Reveal(m) => m.expected_public_key(),
+            Stage::Cosign(m) => m.public_key(),
+            Stage::Transition => return Err(Error::InvalidMuSigStage),
+        };
+
+        Ok(key.to_bytes().to_vec())
+    }
+
+    /// Get this cosigner's 16-byte commitment, to be shared during the first round
+    pub fn commitment(&self) -> Result<Vec<u8>, Error> {
+        match &self.stage {
+            Stage::Commit(m) => Ok(m.our_commitment().0.to_vec()),
+            _ => Err(Error::InvalidMuSigStage),
+        }
+    }
+
+    /// Add another cosigner's commitment
+    ///
+    /// * pubkey: 32-byte public key of the other cosigner
+    /// * commitment: 16-byte commitment of the other cosigner
+    #[wasm_bindgen(js_name = addCommitment)]
+    pub fn add_commitment(&mut self, pubkey: &[u8], commitment: &[u8]) -> Result<(), Error> {
+        let commitment = Commitment(
+            commitment
+                .try_into()
+                .map_err(|_| Error::InvalidContribution)?,
+        );
+        match &mut self.stage {
+            Stage::Commit(m) => m
+                .add_their_commitment(public_key(pubkey)?, commitment)
+                .map_err(|_| Error::InvalidContribution),
+            _ => Err(Error::InvalidMuSigStage),
+        }
+    }
+
+    /// Get this cosigner's 96-byte reveal, to be shared during the second round once the
+    /// commitments of all other cosigners have been added
+    pub fn reveal(&mut self) -> Result<Vec<u8>, Error> {
+        self.stage = match mem::replace(&mut self.stage, Stage::Transition) {
+            Stage::Commit(m) => Stage::Reveal(m.reveal_stage()),
+            stage => stage,
+        };
+
+        match &self.stage {
+            Stage::Reveal(m) => Ok(m.our_reveal().0.to_vec()),
+            _ => Err(Error::InvalidMuSigStage),
+        }
+    }
+
+    /// Add another cosigner's reveal, which must match its commitment
+    ///
+    /// * pubkey: 32-byte public key of the other cosigner
+    /// * reveal: 96-byte reveal of the other cosigner
+    #[wasm_bindgen(js_name = addReveal)]
+    pub fn add_reveal(&mut self, pubkey: &[u8], reveal: &[u8]) -> Result<(), Error> {
+        let reveal = Reveal(reveal.try_into().map_err(|_| Error::InvalidContribution)?);
+        match &mut self.stage {
+            Stage::Reveal(m) => m
+                .add_their_reveal(public_key(pubkey)?, reveal)
+                .map_err(|_| Error::InvalidContribution),
+            _ => Err(Error::InvalidMuSigStage),
+        }
+    }
+
+    /// Get this cosigner's 32-byte cosignature, to be shared during the third round once the
+    /// reveals of all other cosigners have been added
+    pub fn cosign(&mut self) -> Result<Vec<u8>, Error> {
+        self.stage = match mem::replace(&mut self.stage, Stage::Transition) {
+            Stage::Reveal(m) => Stage::Cosign(m.cosign_stage()),
+            stage => stage,
+        };
+
+        match &self.stage {
+            Stage::Cosign(m) => Ok(m.our_cosignature().0.to_vec()),
+            _ => Err(Error::InvalidMuSigStage),
+        }
+    }
+
+    /// Add another cosigner's cosignature
+    ///
+    /// * pubkey: 32-byte public key of the other cosigner
+    /// * cosignature: 32-byte cosignature of the other cosigner
+    #[wasm_bindgen(js_name = addCosignature)]
+    pub fn add_cosignature(&mut self, pubkey: &[u8], cosignature: &[u8]) -> Result<(), Error> {
+        let cosignature = Cosignature(
+            cosignature
+                .try_into()
+                .map_err(|_| Error::InvalidContribution)?,
+        );
+        match &mut self.stage {
+            Stage::Cosign(m) => m
+                .add_their_cosignature(public_key(pubkey)?, cosignature)
+                .map_err(|_| Error::InvalidContribution),
+            _ => Err(Error::InvalidMuSigStage),
+        }
+    }
+
+    /// Aggregate the cosignatures once those of all other cosigners have been added
+    ///
+    /// Returns a 64-byte sr25519 signature
+    pub fn sign(&self) -> Result<Vec<u8>, Error> {
+        match &self.stage {
+            Stage::Cosign(m) => m
+                .sign()
+                .map(|s| s.to_bytes().to_vec())
+                .ok_or(Error::InvalidMuSigStage),
+            _ => Err(Error::InvalidMuSigStage),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use error::Error;
+
+    use crate::{keypair_from_suri, musig_public_key, verify, MuSigSession};
+
+    #[test]
+    fn signs_jointly() {
+        let keypairs: Vec<_> = ["//Alice", "//Bob", "//Charlie"]
+            .iter()
+            .map(|suri| keypair_from_suri(suri).unwrap())
+            .collect();
+        let mut sessions: Vec<_> = keypairs
+            .iter()
+            .map(|k| MuSigSession::new(&k[64..], &k[..64], b"dots").unwrap())
+            .collect();
+
+        assert_eq!(sessions[0].cosign(), Err(Error::InvalidMuSigStage));
+
+        let commitments: Vec<_> = sessions.iter().map(|s| s.commitment().unwrap()).collect();
+        for (i, session) in sessions.iter_mut().enumerate() {
+            for (j, keypair) in keypairs.iter().enumerate().filter(|(j, _)| *j != i) {
+                session
+                    .add_commitment(&keypair[64..], &commitments[j])
+                    .unwrap();
+            }
+        }
+
+        let reveals: Vec<_> = sessions.iter_mut().map(|s| s.reveal().unwrap()).collect();
+        assert_eq!(
+            sessions[0].add_reveal(&keypairs[1][64..], &reveals[2]),
+            Err(Error::InvalidContribution)
+        );
+        for (i, session) in sessions.iter_mut().enumerate() {
+            for (j, keypair) in keypairs.iter().enumerate().filter(|(j, _)| *j != i) {
+                session.add_reveal(&keypair[64..], &reveals[j]).unwrap();
+            }
+        }
+
+        let cosignatures: Vec<_> = sessions.iter_mut().map(|s| s.cosign().unwrap()).collect();
+        assert_eq!(sessions[0].sign(), Err(Error::InvalidMuSigStage));
+        for (i, session) in sessions.iter_mut().enumerate() {
+            for (j, keypair) in keypairs.iter().enumerate().filter(|(j, _)| *j != i) {
+                session
+                    .add_cosignature(&keypair[64..], &cosignatures[j])
+                    .unwrap();
+            }
+        }
+
+        let public_keys: Vec<u8> = keypairs.iter().flat_map(|k| k[64..].to_vec()).collect();
+        let public_key = musig_public_key(&public_keys).unwrap();
+        let signature = sessions[0].sign().unwrap();
+
+        for session in &sessions {
+            assert_eq!(session.public_key().unwrap(), public_key);
+            assert_eq!(session.sign().unwrap(), signature);
+        }
+        assert!(verify(&public_key, b"dots", &signature));
+        assert!(!verify(&public_key, b"tods", &signature));
+    }
+}
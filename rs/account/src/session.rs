@@ -0,0 +1,157 @@
+use crypto_secretbox::aead::{rand_core::RngCore, OsRng};
+use error::Error;
+use wasm_bindgen::prelude::*;
+use zeroize::Zeroizing;
+
+use crate::{ecdsa, ed25519, keypair_from_secret, KeyType};
+
+/// A newly generated session key
+#[wasm_bindgen]
+#[derive(Clone)]
+pub struct SessionKey {
+    key_type_id: String,
+    key_type: KeyType,
+    secret: Zeroizing<Vec<u8>>,
+    public_key: Vec<u8>,
+}
+
+#[wasm_bindgen]
+impl SessionKey {
+    /// Four-character key type ID (e.g. `babe`)
+    #[wasm_bindgen(getter, js_name = keyTypeId)]
+    pub fn key_type_id(&self) -> String {
+        self.key_type_id.clone()
+    }
+
+    #[wasm_bindgen(getter, js_name = keyType)]
+    pub fn key_type(&self) -> KeyType {
+        self.key_type
+    }
+
+    /// Secret URI of the key (i.e. the `0x`-prefixed hex secret), as expected by `author_insertKey`
+    #[wasm_bindgen(getter)]
+    pub fn suri(&self) -> String {
+        format!("0x{}", hex::encode(&self.secret[..]))
+    }
+
+    #[wasm_bindgen(getter, js_name = publicKey)]
+    pub fn public_key(&self) -> Vec<u8> {
+        self.public_key.clone()
+    }
+}
+
+/// A set of session keys for a validator
+#[wasm_bindgen]
+#[derive(Default)]
+pub struct SessionKeys {
+    keys: Vec<SessionKey>,
+}
+
+#[wasm_bindgen]
+impl SessionKeys {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> SessionKeys {
+        SessionKeys::default()
+    }
+
+    /// Generate the session keys of a Polkadot or Kusama validator
+    ///
+    /// Returns keys for GRANDPA, BABE, ImOnline, parachain validation, parachain assignment and
+    /// authority discovery, in the order of the relay chain runtime's `SessionKeys`
+    pub fn polkadot() -> Result<SessionKeys, Error> {
+        let mut keys = SessionKeys::new();
+        keys.add("gran", KeyType::Ed25519)?;
+        for key_type_id in ["babe", "imon", "para", "asgn", "audi"] {
+            keys.add(key_type_id, KeyType::Sr25519)?;
+        }
+
+        Ok(keys)
+    }
+
+    /// Generate a key and add it to the end of the set
+    ///
+    /// * key_type_id: four-character key type ID (e.g. `babe`)
+    /// * key_type: type of the key
+    pub fn add(&mut self, key_type_id: &str, key_type: KeyType) -> Result<(), Error> {
+        if key_type_id.len() != 4 || !key_type_id.bytes().all(|b| b.is_ascii_graphic()) {
+            return Err(Error::InvalidKeyTypeId);
+        }
+
+        let mut secret = Zeroizing::new(vec![0u8; 32]);
+        OsRng.fill_bytes(&mut secret);
+
+        let keypair = Zeroizing::new(match key_type {
+            KeyType::Sr25519 => keypair_from_secret(&secret)?,
+            KeyType::Ed25519 => ed25519::keypair_from_secret(&secret)?,
+            KeyType::Ecdsa => ecdsa::keypair_from_secret(&secret)?,
+        });
+        let (private_length, _) = key_type.key_lengths();
+
+        self.keys.push(SessionKey {
+            key_type_id: key_type_id.to_string(),
+            key_type,
+            secret,
+            public_key: keypair[private_length..].to_vec(),
+        });
+        Ok(())
+    }
+
+    /// Number of keys in the set
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Get a key by its position in the set
+    pub fn key(&self, index: usize) -> Option<SessionKey> {
+        self.keys.get(index).cloned()
+    }
+
+    /// SCALE-encoded keys to be passed to `session.setKeys`, which is the concatenation of the
+    /// public keys in the order in which they were added
+    #[wasm_bindgen(getter)]
+    pub fn keys(&self) -> Vec<u8> {
+        self.keys
+            .iter()
+            .flat_map(|k| k.public_key.clone())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use error::Error;
+
+    use crate::{ecdsa, ed25519, keypair_from_suri, KeyType, SessionKeys};
+
+    #[test]
+    fn generates_session_keys() {
+        let mut keys = SessionKeys::polkadot().unwrap();
+        keys.add("beef", KeyType::Ecdsa).unwrap();
+
+        assert_eq!(keys.length(), 7);
+        assert_eq!(keys.keys().len(), 6 * 32 + 33);
+
+        let mut offset = 0;
+        for index in 0..keys.length() {
+            let key = keys.key(index).unwrap();
+            let keypair = match key.key_type() {
+                KeyType::Sr25519 => keypair_from_suri(&key.suri()).unwrap(),
+                KeyType::Ed25519 => ed25519::keypair_from_suri(&key.suri()).unwrap(),
+                KeyType::Ecdsa => ecdsa::keypair_from_suri(&key.suri()).unwrap(),
+            };
+            let public_key = &keypair[key.key_type().key_lengths().0..];
+
+            assert_eq!(key.public_key(), public_key);
+            assert_eq!(keys.keys()[offset..offset + public_key.len()], *public_key);
+            offset += public_key.len();
+        }
+
+        assert_eq!(keys.key(0).unwrap().key_type_id(), "gran");
+        assert!(keys.key(7).is_none());
+        assert_eq!(
+            keys.add("babe1", KeyType::Sr25519),
+            Err(Error::InvalidKeyTypeId)
+        );
+    }
+}
@@ -0,0 +1,295 @@
+use bip39::Language;
+use crypto_secretbox::aead::{rand_core::RngCore, OsRng};
+use error::Error;
+use sharks::{Share, Sharks};
+use wasm_bindgen::prelude::*;
+use zeroize::Zeroizing;
+
+use crate::{
+    blake2_256, mnemonic_from_phrase, phrase_from_entropy, phrase_language, PhraseLanguage,
+};
+
+/// Largest number of shares that are needed to recover a phrase
+const MAX_THRESHOLD: u8 = 16;
+/// Largest number of shares that a phrase may be split into
+const MAX_SHARES: u8 = 127;
+/// Number of words that precede the phrase of a share
+const HEADER_WORDS: usize = 5;
+/// Length of the digest that is shared along with the entropy
+const DIGEST_LENGTH: usize = 4;
+
+/// The words that precede the phrase of a share, which encode (from the most significant bit)
+/// the threshold less one in 4 bits, the index of the share in 7 bits, the identifier of the
+/// split in 12 bits and the share of the digest in 32 bits
+struct Header {
+    threshold: u8,
+    index: u8,
+    identifier: u16,
+    digest: [u8; DIGEST_LENGTH],
+}
+
+impl Header {
+    fn to_words(&self, language: Language) -> String {
+        let bits = (self.threshold as u64 - 1) << 51
+            | (self.index as u64) << 44
+            | (self.identifier as u64) << 32
+            | u32::from_be_bytes(self.digest) as u64;
+
+        (0..HEADER_WORDS)
+            .rev()
+            .map(|i| {
+                let word = (bits >> (11 * i)) as u16 & 0x7ff;
+                language.wordlist().get_word(word.into())
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn from_words(words: &[&str], language: Language) -> Result<Header, Error> {
+        let wordmap = language.wordmap();
+        let mut bits = 0u64;
+        for word in words {
+            let word: u16 = wordmap
+                .get_bits(word)
+                .map_err(|_| Error::InvalidShare)?
+                .into();
+            bits = bits << 11 | word as u64;
+        }
+
+        let header = Header {
+            threshold: (bits >> 51) as u8 + 1,
+            index: (bits >> 44) as u8 & 0x7f,
+            identifier: (bits >> 32) as u16 & 0xfff,
+            digest: (bits as u32).to_be_bytes(),
+        };
+        match header.threshold >= 2 && header.index != 0 {
+            true => Ok(header),
+            false => Err(Error::InvalidShare),
+        }
+    }
+}
+
+/// Digest of the entropy that lets `combine_shares` detect shares of different splits
+fn digest(identifier: u16, entropy: &[u8]) -> [u8; DIGEST_LENGTH] {
+    let hash = blake2_256(&[&identifier.to_be_bytes(), entropy].concat());
+    hash[..DIGEST_LENGTH]
+        .try_into()
+        .expect("the hash is longer")
+}
+
+/// Parse a share into its header, its language and its Shamir share of the entropy and digest
+fn parse_share(share: &str) -> Result<(Header, PhraseLanguage, Share), Error> {
+    let words: Vec<&str> = share.split_whitespace().collect();
+    if words.len() <= HEADER_WORDS {
+        return Err(Error::InvalidShare);
+    }
+
+    let phrase = words[HEADER_WORDS..].join(" ");
+    let language = phrase_language(&phrase).map_err(|_| Error::InvalidShare)?;
+    let header = Header::from_words(&words[..HEADER_WORDS], language.into())?;
+
+    let y = Zeroizing::new(
+        mnemonic_from_phrase(&phrase)
+            .map_err(|_| Error::InvalidShare)?
+            .entropy()
+            .to_vec(),
+    );
+    let share = Share::try_from(&[&[header.index], &y[..], &header.digest].concat()[..])
+        .map_err(|_| Error::InvalidShare)?;
+    Ok((header, language, share))
+}
+
+/// Split a bip39 phrase into shares, any `threshold` of which recover the phrase
+///
+/// The entropy of the phrase and a digest of it are split with Shamir's secret sharing over
+/// GF(256). Each share is written as five header words that encode the threshold, the index of
+/// the share, a random identifier of the split and the share of the digest, followed by a bip39
+/// phrase of the same length and language as the original, whose checksum catches most
+/// transcription errors. The shares are not compatible with SLIP-39.
+///
+/// * phrase: mnemonic phrase in any supported language
+/// * threshold: number of shares that are needed to recover the phrase, from 2 to 16
+/// * shares: number of shares to create, from `threshold` to 127
+///
+/// Returns the shares, separated by newlines
+#[wasm_bindgen(js_name = splitPhrase)]
+pub fn split_phrase(phrase: &str, threshold: u8, shares: u8) -> Result<String, Error> {
+    if !(2..=MAX_THRESHOLD).contains(&threshold) || !(threshold..=MAX_SHARES).contains(&shares) {
+        return Err(Error::InvalidThreshold);
+    }
+
+    let language = phrase_language(phrase)?;
+    let entropy = Zeroizing::new(mnemonic_from_phrase(phrase)?.entropy().to_vec());
+    let identifier = OsRng.next_u32() as u16 & 0xfff;
+    let secret = Zeroizing::new([&entropy[..], &digest(identifier, &entropy)].concat());
+
+    Sharks(threshold)
+        .dealer(&secret)
+        .take(shares.into())
+        .map(|share| {
+            let bytes = Zeroizing::new(Vec::from(&share));
+            let (y, digest) = bytes[1..].split_at(entropy.len());
+            let header = Header {
+                threshold,
+                index: bytes[0],
+                identifier,
+                digest: digest.try_into().expect("the digest is shared"),
+            };
+
+            Ok(format!(
+                "{} {}",
+                header.to_words(language.into()),
+                phrase_from_entropy(y, language)?
+            ))
+        })
+        .collect::<Result<Vec<_>, Error>>()
+        .map(|shares| shares.join("\n"))
+}
+
+/// Recover a bip39 phrase from the shares that were created by `split_phrase`
+///
+/// shares: at least as many distinct shares as the threshold, separated by newlines
+///
+/// Returns the original phrase, or an error if the shares are not all from the same split
+#[wasm_bindgen(js_name = combineShares)]
+pub fn combine_shares(shares: &str) -> Result<String, Error> {
+    let mut parsed: Vec<Share> = Vec::new();
+    let mut split = None;
+
+    for share in shares.lines().filter(|l| !l.trim().is_empty()) {
+        let (header, language, share) = parse_share(share)?;
+        let this = (header.threshold, header.identifier, language);
+        if *split.get_or_insert(this) != this {
+            return Err(Error::InvalidShare);
+        }
+
+        match parsed.iter().find(|s| s.x.0 == share.x.0) {
+            // the same share may be given more than once, but two shares may not have the same index
+            Some(s) if Vec::from(s) == Vec::from(&share) => continue,
+            Some(_) => return Err(Error::InvalidShare),
+            None => parsed.push(share),
+        }
+    }
+
+    let (threshold, identifier, language) = split.ok_or(Error::InsufficientShares)?;
+    if parsed.len() < threshold.into() {
+        return Err(Error::InsufficientShares);
+    }
+    if parsed.iter().any(|s| s.y.len() != parsed[0].y.len()) {
+        return Err(Error::InvalidShare);
+    }
+
+    let secret = Zeroizing::new(
+        Sharks(threshold)
+            .recover(&parsed)
+            .map_err(|_| Error::InsufficientShares)?,
+    );
+
+    // shares of different splits (or with a corrupted header) interpolate to a wrong secret
+    let (entropy, expected) = secret.split_at(secret.len() - DIGEST_LENGTH);
+    if digest(identifier, entropy) != expected {
+        return Err(Error::InvalidShare);
+    }
+
+    phrase_from_entropy(entropy, language)
+}
+
+#[cfg(test)]
+mod tests {
+    use error::Error;
+
+    use crate::{
+        combine_shares, convert_phrase, entropy_from_phrase, keypair_from_secret,
+        secret_from_phrase, split_phrase, PhraseLanguage, DEV_PHRASE,
+    };
+
+    #[test]
+    fn splits_phrases() {
+        let shares = split_phrase(DEV_PHRASE, 3, 5).unwrap();
+        let shares: Vec<&str> = shares.lines().collect();
+        assert_eq!(shares.len(), 5);
+        assert!(shares.iter().all(|s| s.split(' ').count() == 17));
+
+        for subset in [[0, 1, 2], [4, 2, 0], [1, 3, 4]] {
+            let subset: Vec<&str> = subset.iter().map(|i| shares[*i]).collect();
+            let phrase = combine_shares(&subset.join("\n")).unwrap();
+
+            assert_eq!(phrase, DEV_PHRASE);
+            assert_eq!(
+                entropy_from_phrase(&phrase).unwrap(),
+                entropy_from_phrase(DEV_PHRASE).unwrap()
+            );
+            assert_eq!(
+                keypair_from_secret(&secret_from_phrase(&phrase, "").unwrap()).unwrap(),
+                keypair_from_secret(&secret_from_phrase(DEV_PHRASE, "").unwrap()).unwrap()
+            );
+        }
+        assert_eq!(combine_shares(&shares.join("\n")).unwrap(), DEV_PHRASE);
+
+        let french = convert_phrase(DEV_PHRASE, PhraseLanguage::French).unwrap();
+        let shares = split_phrase(&french, 2, 2).unwrap();
+        assert_eq!(combine_shares(&shares).unwrap(), french);
+    }
+
+    #[test]
+    fn rejects_invalid_shares() {
+        assert_eq!(split_phrase(DEV_PHRASE, 1, 5), Err(Error::InvalidThreshold));
+        assert_eq!(split_phrase(DEV_PHRASE, 3, 2), Err(Error::InvalidThreshold));
+        assert_eq!(
+            split_phrase(DEV_PHRASE, 17, 20),
+            Err(Error::InvalidThreshold)
+        );
+
+        let shares = split_phrase(DEV_PHRASE, 3, 5).unwrap();
+        let shares: Vec<&str> = shares.lines().collect();
+        assert_eq!(
+            combine_shares(&[shares[0], shares[1], shares[0]].join("\n")),
+            Err(Error::InsufficientShares)
+        );
+        assert_eq!(combine_shares(""), Err(Error::InsufficientShares));
+        assert_eq!(
+            combine_shares(&[shares[0], DEV_PHRASE].join("\n")),
+            Err(Error::InvalidShare)
+        );
+
+        let other = split_phrase(DEV_PHRASE, 2, 2).unwrap();
+        let other: Vec<&str> = other.lines().collect();
+        assert_eq!(
+            combine_shares(&[shares[0], shares[1], other[1]].join("\n")),
+            Err(Error::InvalidShare)
+        );
+
+        // a corrupted share of the digest is only caught after recovery
+        let mut words: Vec<&str> = shares[2].split(' ').collect();
+        words[4] = if words[4] == "abandon" {
+            "ability"
+        } else {
+            "abandon"
+        };
+        assert_eq!(
+            combine_shares(&[shares[0], shares[1], &words.join(" ")].join("\n")),
+            Err(Error::InvalidShare)
+        );
+    }
+
+    #[test]
+    fn rejects_mixed_splits() {
+        let phrase = "legal winner thank year wave sausage worth useful legal winner thank yellow";
+
+        for other in [phrase, DEV_PHRASE] {
+            let shares = split_phrase(DEV_PHRASE, 3, 5).unwrap();
+            let shares: Vec<&str> = shares.lines().collect();
+            let others = split_phrase(other, 3, 5).unwrap();
+            let others: Vec<&str> = others.lines().collect();
+
+            assert_eq!(
+                combine_shares(&[shares[0], shares[1], others[2]].join("\n")),
+                Err(Error::InvalidShare)
+            );
+            assert_eq!(
+                combine_shares(&[others[3], shares[4], others[0]].join("\n")),
+                Err(Error::InvalidShare)
+            );
+        }
+    }
+}
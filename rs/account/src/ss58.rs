@@ -0,0 +1,213 @@
+use base58::{FromBase58, ToBase58};
+use blake2_rfc::blake2b::Blake2b;
+use error::Error;
+use ss58_registry::Ss58AddressFormat;
+use wasm_bindgen::prelude::*;
+
+/// Ss58 formats that Substrate reserves and does not accept in addresses
+const RESERVED_FORMATS: [u16; 2] = [46, 47];
+
+/// The public key and network of a decoded Ss58 address
+#[wasm_bindgen]
+#[derive(Debug, PartialEq, Eq)]
+pub struct DecodedAddress {
+    public_key: Vec<u8>,
+    format: u16,
+}
+
+#[wasm_bindgen]
+impl DecodedAddress {
+    #[wasm_bindgen(getter, js_name = publicKey)]
+    pub fn public_key(&self) -> Vec<u8> {
+        self.public_key.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn format(&self) -> u16 {
+        self.format
+    }
+}
+
+/// The first two bytes of the blake2b-512 hash of the Ss58 pre-image
+fn checksum(bytes: &[u8]) -> [u8; 2] {
+    let mut context = Blake2b::new(64);
+    context.update(b"SS58PRE");
+    context.update(bytes);
+
+    let hash = context.finalize();
+    [hash.as_bytes()[0], hash.as_bytes()[1]]
+}
+
+/// Encode an Ss58 format as the one or two bytes that prefix an address
+pub(crate) fn format_prefix(format: u16) -> Result<Vec<u8>, Error> {
+    let network = Ss58AddressFormat::from(format);
+    let prefix = u16::from(network);
+    if RESERVED_FORMATS.contains(&prefix) {
+        return Err(Error::UnknownPrefix);
+    }
+
+    match prefix {
+        0..=63 => Ok(vec![prefix as u8]),
+        64..=16_383 => {
+            let first = ((prefix & 0b0000_0000_1111_1100) as u8) >> 2;
+            let second = ((prefix >> 8) as u8) | ((prefix & 0b0000_0000_0000_0011) as u8) << 6;
+
+            Ok(vec![first | 0b01000000, second])
+        }
+        _ => Err(Error::UnsupportedNetwork),
+    }
+}
+
+/// Create an Ss58 address from a public key and an Ss58 format
+///
+/// public_key: 32-byte public key
+/// format: Ss58 format
+///
+/// Returns the Ss58 address for the public key and format
+///
+/// ref: https://github.com/paritytech/substrate/blob/monthly-2022-09/primitives/core/src/crypto.rs#L317:L338
+#[wasm_bindgen(js_name = addressFromPublicKey)]
+pub fn address_from_public_key(public_key: &[u8], format: u16) -> Result<String, Error> {
+    let mut bytes = format_prefix(format)?;
+    bytes.extend(public_key);
+
+    let checksum = checksum(&bytes);
+    bytes.extend(checksum);
+
+    Ok(bytes.to_base58())
+}
+
+/// Decode an Ss58 address
+///
+/// address: Ss58 address
+///
+/// Returns the 32-byte public key and the Ss58 format of the address
+///
+/// ref: https://github.com/paritytech/substrate/blob/monthly-2022-09/primitives/core/src/crypto.rs
+#[wasm_bindgen(js_name = decodeAddress)]
+pub fn decode_address(address: &str) -> Result<DecodedAddress, Error> {
+    let data = address.from_base58().map_err(|_| Error::InvalidBase58)?;
+
+    if data.len() < 2 {
+        return Err(Error::InvalidAddressLength);
+    }
+
+    let (prefix_len, format) = match data[0] {
+        0..=63 => (1, data[0] as u16),
+        64..=127 => {
+            let lower = (data[0] << 2) | (data[1] >> 6);
+            let upper = data[1] & 0b0011_1111;
+            let format = (lower as u16) | ((upper as u16) << 8);
+
+            // formats below 64 are always encoded with one byte, so that each account has a
+            // single address per format
+            if format < 64 {
+                return Err(Error::InvalidPrefix);
+            }
+
+            (2, format)
+        }
+        _ => return Err(Error::UnknownPrefix),
+    };
+
+    if RESERVED_FORMATS.contains(&format) {
+        return Err(Error::UnknownPrefix);
+    }
+
+    if data.len() != prefix_len + 32 + 2 {
+        return Err(Error::InvalidAddressLength);
+    }
+
+    let (body, check) = data.split_at(data.len() - 2);
+    if checksum(body) != check {
+        return Err(Error::InvalidChecksum);
+    }
+
+    Ok(DecodedAddress {
+        public_key: body[prefix_len..].to_vec(),
+        format,
+    })
+}
+
+/// Re-encode an Ss58 address for another network
+///
+/// address: Ss58 address
+/// format: Ss58 format of the new address
+///
+/// Returns the Ss58 address for the same public key and the given format
+#[wasm_bindgen(js_name = convertAddress)]
+pub fn convert_address(address: &str, format: u16) -> Result<String, Error> {
+    address_from_public_key(&decode_address(address)?.public_key, format)
+}
+
+#[cfg(test)]
+mod tests {
+    use base58::ToBase58;
+
+    use error::Error;
+
+    use crate::ss58::{checksum, convert_address, decode_address};
+
+    const ALICE: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+
+    #[test]
+    fn round_trips() {
+        let decoded = decode_address(ALICE).unwrap();
+        assert_eq!(decoded.format(), 42);
+        assert_eq!(
+            hex::encode(decoded.public_key()),
+            "d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"
+        );
+
+        let polkadot = convert_address(ALICE, 0).unwrap();
+        assert_eq!(polkadot, "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5");
+
+        let two_byte = convert_address(ALICE, 1_000).unwrap();
+        assert_eq!(decode_address(&two_byte).unwrap().format(), 1_000);
+        assert_eq!(convert_address(&two_byte, 42).unwrap(), ALICE);
+    }
+
+    #[test]
+    fn rejects_invalid_addresses() {
+        assert_eq!(decode_address("0OIl").unwrap_err(), Error::InvalidBase58);
+        assert_eq!(
+            decode_address(&[42u8; 16].to_base58()).unwrap_err(),
+            Error::InvalidAddressLength
+        );
+        assert_eq!(
+            decode_address("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQZ").unwrap_err(),
+            Error::InvalidChecksum
+        );
+        assert_eq!(
+            decode_address(&[255u8; 35].to_base58()).unwrap_err(),
+            Error::UnknownPrefix
+        );
+        for format in [46, 47] {
+            let mut bytes = vec![format];
+            bytes.extend([0u8; 32]);
+            bytes.extend(checksum(&bytes));
+            assert_eq!(
+                decode_address(&bytes.to_base58()).unwrap_err(),
+                Error::UnknownPrefix
+            );
+            assert_eq!(
+                convert_address(ALICE, format.into()).unwrap_err(),
+                Error::UnknownPrefix
+            );
+        }
+
+        // format 42 in the two-byte form, which `format_prefix` never produces
+        let mut bytes = vec![0b0100_1010, 0b1000_0000];
+        bytes.extend(decode_address(ALICE).unwrap().public_key());
+        bytes.extend(checksum(&bytes));
+        assert_eq!(
+            decode_address(&bytes.to_base58()).unwrap_err(),
+            Error::InvalidPrefix
+        );
+
+        assert_eq!(
+            convert_address(ALICE, 16_384).unwrap_err(),
+            Error::UnsupportedNetwork
+        );
+    }
+}
@@ -0,0 +1,116 @@
+use error::Error;
+use parity_scale_codec::Encode;
+
+use crate::{blake2_256, secret_from_phrase};
+
+/// The phrase that Substrate uses for its well-known development accounts (e.g. `//Alice`)
+///
+/// ref: https://github.com/paritytech/substrate/blob/monthly-2022-09/primitives/core/src/crypto.rs#L39
+pub const DEV_PHRASE: &str =
+    "bottom drive obey lake curtain smoke basket hold race lonely fit walk";
+
+/// A single step in a derivation path; the 32 bytes are the junction's chain code
+///
+/// ref: https://github.com/paritytech/substrate/blob/monthly-2022-09/primitives/core/src/crypto.rs#L142:L219
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DeriveJunction {
+    Soft([u8; 32]),
+    Hard([u8; 32]),
+}
+
+impl DeriveJunction {
+    /// Create a junction from its textual form (without the leading slashes)
+    ///
+    /// Numeric junctions are SCALE-encoded as a `u64`, all others as a string; encodings that do
+    /// not fit in 32 bytes are hashed with blake2b-256, shorter ones are zero-padded.
+    fn new(code: &str, hard: bool) -> Self {
+        let encoded = match code.parse::<u64>() {
+            Ok(n) => n.encode(),
+            _ => code.encode(),
+        };
+
+        let cc = if encoded.len() > 32 {
+            blake2_256(&encoded)
+        } else {
+            let mut cc = [0u8; 32];
+            cc[..encoded.len()].copy_from_slice(&encoded);
+            cc
+        };
+
+        if hard {
+            DeriveJunction::Hard(cc)
+        } else {
+            DeriveJunction::Soft(cc)
+        }
+    }
+}
+
+/// Parse a derivation path such as `//hard/soft` into its junctions
+///
+/// Returns `None` if the path is malformed (e.g. it contains an empty junction)
+pub fn parse_path(path: &str) -> Option<Vec<DeriveJunction>> {
+    let mut junctions = vec![];
+    let mut rest = path;
+
+    while !rest.is_empty() {
+        rest = rest.strip_prefix('/')?;
+        let hard = match rest.strip_prefix('/') {
+            Some(r) => {
+                rest = r;
+                true
+            }
+            None => false,
+        };
+
+        let end = rest.find('/').unwrap_or(rest.len());
+        if end == 0 {
+            return None;
+        }
+
+        junctions.push(DeriveJunction::new(&rest[..end], hard));
+        rest = &rest[end..];
+    }
+
+    Some(junctions)
+}
+
+/// A secret URI of the form `phrase//hard/soft///password`
+///
+/// The phrase may be a bip39 phrase or a 0x-prefixed hex secret; if it is omitted, the
+/// development phrase is used.
+///
+/// ref: https://github.com/paritytech/substrate/blob/monthly-2022-09/primitives/core/src/crypto.rs#L1001:L1043
+pub struct Suri<'a> {
+    pub phrase: &'a str,
+    pub path: Vec<DeriveJunction>,
+    pub password: Option<&'a str>,
+}
+
+impl<'a> Suri<'a> {
+    pub fn parse(suri: &'a str) -> Result<Self, Error> {
+        let (rest, password) = match suri.find("///") {
+            Some(i) => (&suri[..i], Some(&suri[i + 3..])),
+            None => (suri, None),
+        };
+
+        let (phrase, path) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+        let phrase = match phrase.trim() {
+            "" => DEV_PHRASE,
+            p => p,
+        };
+
+        Ok(Suri {
+            phrase,
+            path: parse_path(path).ok_or(Error::InvalidSecretUri)?,
+            password,
+        })
+    }
+
+    /// Returns the 32-byte secret for the phrase (and password) of the secret URI
+    pub fn secret(&self) -> Result<Vec<u8>, Error> {
+        match self.phrase.strip_prefix("0x") {
+            Some(hex) => hex::decode(hex).map_err(|_| Error::InvalidSecret),
+            None => secret_from_phrase(self.phrase, self.password.unwrap_or("")),
+        }
+    }
+}
@@ -0,0 +1,190 @@
+use std::{collections::BTreeSet, sync::Mutex};
+
+use error::Error;
+use merlin::Transcript;
+use schnorrkel::{PublicKey, SecretKey, Signature};
+use wasm_bindgen::prelude::*;
+
+/// Labels of the transcripts that Substrate uses, which never need to be copied
+const KNOWN_LABELS: [&[u8]; 8] = [
+    b"",
+    b"SigningContext",
+    b"sign-bytes",
+    b"substrate",
+    b"BABE",
+    b"slot number",
+    b"current epoch",
+    b"chain randomness",
+];
+
+/// Largest number of other labels that may be copied
+const MAX_LABELS: usize = 256;
+
+/// Labels that have been copied
+struct Labels(BTreeSet<&'static [u8]>);
+
+impl Labels {
+    /// Get a `'static` copy of a label, as required by merlin
+    ///
+    /// Labels other than the known ones are leaked, once each, so at most `MAX_LABELS` of them are
+    /// accepted in order to bound the memory that they use.
+    fn intern(&mut self, label: &[u8]) -> Result<&'static [u8], Error> {
+        if let Some(l) = KNOWN_LABELS.iter().find(|l| **l == label) {
+            return Ok(l);
+        }
+
+        match self.0.get(label) {
+            Some(l) => Ok(l),
+            None if self.0.len() == MAX_LABELS => Err(Error::TooManyLabels),
+            None => {
+                let l: &'static [u8] = Box::leak(label.to_vec().into_boxed_slice());
+                self.0.insert(l);
+                Ok(l)
+            }
+        }
+    }
+}
+
+fn intern(label: &[u8]) -> Result<&'static [u8], Error> {
+    static LABELS: Mutex<Labels> = Mutex::new(Labels(BTreeSet::new()));
+
+    LABELS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .intern(label)
+}
+
+/// A merlin transcript of labeled messages that can be signed in place of a plain message
+#[wasm_bindgen]
+#[derive(Clone)]
+pub struct SigningTranscript {
+    pub(crate) transcript: Transcript,
+}
+
+#[wasm_bindgen]
+impl SigningTranscript {
+    /// Create a transcript
+    ///
+    /// Labels are kept for the lifetime of the program, so only a limited number of distinct
+    /// labels, beyond those that Substrate uses, may be passed to `new` and `append`; they should
+    /// be fixed by the protocol rather than derived from user input
+    ///
+    /// label: name of the protocol
+    #[wasm_bindgen(constructor)]
+    pub fn new(label: &[u8]) -> Result<SigningTranscript, Error> {
+        Ok(SigningTranscript {
+            transcript: Transcript::new(intern(label)?),
+        })
+    }
+
+    /// Append a labeled message to the transcript
+    ///
+    /// * label: label of the message, subject to the same limit as the label of the transcript
+    /// * message: message to be appended
+    pub fn append(&mut self, label: &[u8], message: &[u8]) -> Result<(), Error> {
+        self.transcript.append_message(intern(label)?, message);
+        Ok(())
+    }
+}
+
+/// Sign a transcript
+///
+/// * pubkey: 32-byte public key
+/// * privkey: 64-byte private key
+/// * transcript: transcript to be signed
+///
+/// * Returns a 64-byte signature
+#[wasm_bindgen(js_name = signTranscript)]
+pub fn sign_transcript(
+    pubkey: &[u8],
+    privkey: &[u8],
+    transcript: &SigningTranscript,
+) -> Result<Vec<u8>, Error> {
+    match (
+        SecretKey::from_ed25519_bytes(privkey),
+        PublicKey::from_bytes(pubkey),
+    ) {
+        (Ok(s), Ok(k)) => Ok(s
+            .sign(transcript.transcript.clone(), &k)
+            .to_bytes()
+            .to_vec()),
+        _ => Err(Error::InvalidKey),
+    }
+}
+
+/// Verify a signature of a transcript
+///
+/// * pubkey: 32-byte public key
+/// * transcript: transcript that was signed
+/// * signature: 64-byte signature
+///
+/// * Returns true if the signature is valid for the transcript and public key
+#[wasm_bindgen(js_name = verifyTranscript)]
+pub fn verify_transcript(pubkey: &[u8], transcript: &SigningTranscript, signature: &[u8]) -> bool {
+    match (
+        PublicKey::from_bytes(pubkey),
+        Signature::from_bytes(signature),
+    ) {
+        (Ok(k), Ok(s)) => k.verify(transcript.transcript.clone(), &s).is_ok(),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use error::Error;
+
+    use std::collections::BTreeSet;
+
+    use super::{Labels, MAX_LABELS};
+    use crate::{
+        keypair_from_suri, sign_transcript, sign_with_context, verify, verify_transcript,
+        verify_with_context, SigningTranscript,
+    };
+
+    #[test]
+    fn signs_with_contexts() {
+        let alice = keypair_from_suri("//Alice").unwrap();
+        let (private, public) = (&alice[..64], &alice[64..]);
+
+        let signature = sign_with_context(public, private, b"statement", b"dots").unwrap();
+        assert!(verify_with_context(
+            public,
+            b"statement",
+            b"dots",
+            &signature
+        ));
+        assert!(!verify_with_context(
+            public,
+            b"substrate",
+            b"dots",
+            &signature
+        ));
+        assert!(!verify(public, b"dots", &signature));
+
+        // a signing context is shorthand for a transcript
+        let mut transcript = SigningTranscript::new(b"SigningContext").unwrap();
+        transcript.append(b"", b"substrate").unwrap();
+        transcript.append(b"sign-bytes", b"dots").unwrap();
+        let signature = sign_transcript(public, private, &transcript).unwrap();
+        assert!(verify(public, b"dots", &signature));
+        assert!(verify_transcript(public, &transcript, &signature));
+
+        transcript.append(b"nonce", &[0]).unwrap();
+        assert!(!verify_transcript(public, &transcript, &signature));
+    }
+
+    #[test]
+    fn limits_labels() {
+        let mut labels = Labels(BTreeSet::new());
+        for i in 0..MAX_LABELS {
+            labels.intern(format!("label {}", i).as_bytes()).unwrap();
+        }
+
+        // known and previously used labels are still accepted
+        assert_eq!(labels.intern(b"label 0"), Ok(&b"label 0"[..]));
+        assert_eq!(labels.intern(b"BABE"), Ok(&b"BABE"[..]));
+        assert_eq!(labels.intern(b"another label"), Err(Error::TooManyLabels));
+        assert_eq!(labels.0.len(), MAX_LABELS);
+    }
+}
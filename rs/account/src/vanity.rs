@@ -0,0 +1,368 @@
+use base58::ToBase58;
+use error::Error;
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    address_from_public_key, keypair_from_secret, new_phrase, secret_from_phrase,
+    ss58::format_prefix, PhraseLanguage, PhraseSize,
+};
+
+const BASE58_ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// The alphabet indices that each character of a pattern may match
+fn pattern_digits(pattern: &str, case_sensitive: bool) -> Vec<Vec<usize>> {
+    pattern
+        .chars()
+        .map(|c| {
+            let variants = match case_sensitive {
+                true => vec![c],
+                false => vec![c.to_ascii_lowercase(), c.to_ascii_uppercase()],
+            };
+            let mut digits: Vec<usize> = variants
+                .into_iter()
+                .filter_map(|v| BASE58_ALPHABET.find(v))
+                .collect();
+            digits.dedup();
+            digits
+        })
+        .collect()
+}
+
+/// Whether a string of base58 digits can be extended, with the digits that each remaining
+/// position allows, to a string of the same length as `lowest` and `highest` that lies between them
+fn extends_between(
+    digits: &mut Vec<usize>,
+    allowed: &[Vec<usize>],
+    lowest: &[usize],
+    highest: &[usize],
+) -> bool {
+    let len = lowest.len();
+    if digits.len() > len {
+        return false;
+    }
+
+    let mut low = digits.clone();
+    low.resize(len, 0);
+    let mut high = digits.clone();
+    high.resize(len, BASE58_ALPHABET.len() - 1);
+
+    if low.as_slice() > highest || high.as_slice() < lowest {
+        return false;
+    }
+    // any continuation works once every string that starts with the digits is in range
+    if digits.len() == allowed.len() || (low.as_slice() >= lowest && high.as_slice() <= highest) {
+        return true;
+    }
+
+    allowed[digits.len()].iter().any(|d| {
+        digits.push(*d);
+        let found = extends_between(digits, allowed, lowest, highest);
+        digits.pop();
+        found
+    })
+}
+
+/// Whether a pattern can appear at a position in an address of a format
+///
+/// An address is the base58 encoding of the format's prefix followed by 34 bytes. Each leading
+/// zero byte is encoded as a `1`, and the other bytes as a number between the encodings of those
+/// bytes followed by 34 zero bytes and by 34 `0xff` bytes, which constrains the characters of an
+/// address (e.g. every format-42 address starts with `5`).
+fn pattern_possible(
+    pattern: &str,
+    position: VanityPosition,
+    case_sensitive: bool,
+    format: u16,
+) -> Result<bool, Error> {
+    let prefix = format_prefix(format)?;
+    let pattern = pattern_digits(pattern, case_sensitive);
+    let base58 = |bytes: &[u8]| -> Vec<usize> {
+        bytes
+            .to_base58()
+            .chars()
+            .filter_map(|c| BASE58_ALPHABET.find(c))
+            .collect()
+    };
+
+    // the number of leading zero bytes, and the lowest and highest encodings of the other bytes
+    let zeros = prefix.iter().take_while(|b| **b == 0).count();
+    let rest = &prefix[zeros..];
+    let encodings: Vec<(usize, Vec<usize>, Vec<usize>)> = match rest.is_empty() {
+        false => vec![(
+            zeros,
+            base58(&[rest, &[0; 34]].concat()),
+            base58(&[rest, &[0xff; 34]].concat()),
+        )],
+        // the 34 bytes may themselves start with zero bytes
+        true => (0..=34)
+            .map(|z| match z {
+                34 => (zeros + z, vec![], vec![]),
+                _ => (
+                    zeros + z,
+                    base58(&[&[1], &vec![0; 33 - z][..]].concat()),
+                    base58(&vec![0xff; 34 - z]),
+                ),
+            })
+            .collect(),
+    };
+
+    Ok(encodings.iter().any(|(zeros, lowest, highest)| {
+        // numbers between the two may be encoded with any length between theirs
+        (lowest.len()..=highest.len()).any(|len| {
+            let low = match len == lowest.len() {
+                true => lowest.clone(),
+                false => [vec![1], vec![0; len - 1]].concat(),
+            };
+            let high = match len == highest.len() {
+                true => highest.clone(),
+                false => vec![BASE58_ALPHABET.len() - 1; len],
+            };
+
+            let length = zeros + len;
+            let offsets = match position {
+                _ if pattern.len() > length => 0..0,
+                VanityPosition::Prefix => 0..1,
+                VanityPosition::Suffix => length - pattern.len()..length - pattern.len() + 1,
+                VanityPosition::Anywhere => 0..length - pattern.len() + 1,
+            };
+
+            offsets.into_iter().any(|offset| {
+                let mut allowed: Vec<Vec<usize>> = (0..length)
+                    .map(|i| match i < *zeros {
+                        true => vec![0],
+                        false => (0..BASE58_ALPHABET.len()).collect(),
+                    })
+                    .collect();
+                for (i, digits) in pattern.iter().enumerate() {
+                    allowed[offset + i].retain(|d| digits.contains(d));
+                }
+
+                allowed.iter().all(|a| !a.is_empty())
+                    && extends_between(&mut Vec::new(), &allowed[*zeros..], &low, &high)
+            })
+        })
+    }))
+}
+
+/// Where a vanity pattern must appear in an address
+#[wasm_bindgen(js_name = vanityPosition)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VanityPosition {
+    Prefix,
+    Suffix,
+    Anywhere,
+}
+
+/// An sr25519 account whose address matches a vanity pattern
+#[wasm_bindgen]
+#[derive(Debug)]
+pub struct VanityAccount {
+    phrase: String,
+    address: String,
+    attempts: u64,
+}
+
+#[wasm_bindgen]
+impl VanityAccount {
+    #[wasm_bindgen(getter)]
+    pub fn phrase(&self) -> String {
+        self.phrase.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn address(&self) -> String {
+        self.address.clone()
+    }
+
+    /// Number of phrases that were generated to find this account
+    #[wasm_bindgen(getter)]
+    pub fn attempts(&self) -> u64 {
+        self.attempts
+    }
+}
+
+/// Search for sr25519 accounts whose Ss58 addresses match a pattern
+///
+/// Searching is done in batches so that the caller (e.g. a browser's main thread) can remain
+/// responsive between batches
+#[wasm_bindgen]
+pub struct VanityGenerator {
+    pattern: String,
+    position: VanityPosition,
+    case_sensitive: bool,
+    format: u16,
+    attempts: u64,
+}
+
+#[wasm_bindgen]
+impl VanityGenerator {
+    /// Create a generator
+    ///
+    /// * pattern: text that the address must contain
+    /// * position: where the pattern must appear in the address
+    /// * case_sensitive: whether the case of the pattern must match
+    /// * format: Ss58 format of the addresses
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        pattern: &str,
+        position: VanityPosition,
+        case_sensitive: bool,
+        format: u16,
+    ) -> Result<VanityGenerator, Error> {
+        let valid = pattern.chars().all(|c| {
+            if case_sensitive {
+                BASE58_ALPHABET.contains(c)
+            } else {
+                BASE58_ALPHABET.contains(c.to_ascii_lowercase())
+                    || BASE58_ALPHABET.contains(c.to_ascii_uppercase())
+            }
+        });
+
+        if pattern.is_empty() || !valid {
+            return Err(Error::InvalidPattern);
+        }
+
+        // a search for an impossible pattern would never end
+        if !pattern_possible(pattern, position, case_sensitive, format)? {
+            return Err(Error::InvalidPattern);
+        }
+
+        Ok(VanityGenerator {
+            pattern: if case_sensitive {
+                pattern.to_string()
+            } else {
+                pattern.to_lowercase()
+            },
+            position,
+            case_sensitive,
+            format,
+            attempts: 0,
+        })
+    }
+
+    /// Total number of phrases that have been generated
+    #[wasm_bindgen(getter)]
+    pub fn attempts(&self) -> u64 {
+        self.attempts
+    }
+
+    /// Generate up to `batch` new 12-word phrases
+    ///
+    /// Returns the first matching account, or `undefined` if none of the phrases in the batch
+    /// matched
+    pub fn search(&mut self, batch: u32) -> Result<Option<VanityAccount>, Error> {
+        for _ in 0..batch {
+            self.attempts += 1;
+
+            let phrase = new_phrase(PhraseSize::Words12, PhraseLanguage::English);
+            let keypair = keypair_from_secret(&secret_from_phrase(&phrase, "")?)?;
+            let address = address_from_public_key(&keypair[64..], self.format)?;
+
+            if self.matches(&address) {
+                return Ok(Some(VanityAccount {
+                    phrase,
+                    address,
+                    attempts: self.attempts,
+                }));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+impl VanityGenerator {
+    fn matches(&self, address: &str) -> bool {
+        let address = if self.case_sensitive {
+            address.to_string()
+        } else {
+            address.to_lowercase()
+        };
+
+        match self.position {
+            VanityPosition::Prefix => address.starts_with(&self.pattern),
+            VanityPosition::Suffix => address.ends_with(&self.pattern),
+            VanityPosition::Anywhere => address.contains(&self.pattern),
+        }
+    }
+}
+
+/// Search until a matching account is found or an error occurs
+///
+/// `new` rejects patterns that no address can match, so a match is eventually found
+impl Iterator for VanityGenerator {
+    type Item = Result<VanityAccount, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.search(1) {
+                Ok(Some(account)) => return Some(Ok(account)),
+                Ok(None) => continue,
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use error::Error;
+
+    use crate::{keypair_from_secret, secret_from_phrase, VanityGenerator, VanityPosition};
+
+    #[test]
+    fn finds_matches() {
+        let mut generator = VanityGenerator::new("5", VanityPosition::Prefix, true, 42).unwrap();
+        assert_eq!(generator.next().unwrap().unwrap().attempts(), 1);
+
+        let mut generator = VanityGenerator::new("a", VanityPosition::Anywhere, false, 42).unwrap();
+        let account = generator.next().unwrap().unwrap();
+        assert!(account.address().to_lowercase().contains('a'));
+        assert_eq!(account.attempts(), generator.attempts());
+
+        let keypair = keypair_from_secret(&secret_from_phrase(&account.phrase(), "").unwrap());
+        assert!(keypair.is_ok());
+    }
+
+    #[test]
+    fn rejects_invalid_patterns() {
+        assert!(matches!(
+            VanityGenerator::new("0", VanityPosition::Prefix, true, 42),
+            Err(Error::InvalidPattern)
+        ));
+        assert!(matches!(
+            VanityGenerator::new("", VanityPosition::Prefix, true, 42),
+            Err(Error::InvalidPattern)
+        ));
+        assert!(VanityGenerator::new("l", VanityPosition::Anywhere, false, 42).is_ok());
+
+        // every format-42 address starts with `5`, and every Polkadot address with `1`
+        for (pattern, position, format) in [
+            ("4", VanityPosition::Prefix, 42),
+            ("5a", VanityPosition::Prefix, 42),
+            ("5c", VanityPosition::Prefix, 42),
+            ("2", VanityPosition::Prefix, 0),
+            (&"a".repeat(49), VanityPosition::Suffix, 42),
+            // the second character of a format-42 address is between `C` and `H`
+            (&"a".repeat(47), VanityPosition::Suffix, 42),
+            (&"a".repeat(48), VanityPosition::Anywhere, 42),
+            (&"2".repeat(48), VanityPosition::Suffix, 0),
+        ] {
+            assert!(matches!(
+                VanityGenerator::new(pattern, position, true, format),
+                Err(Error::InvalidPattern)
+            ));
+        }
+        for (pattern, format) in [("5Grw", 42), ("5C", 42), ("5H", 42), ("1", 0), ("15oF", 0)] {
+            assert!(VanityGenerator::new(pattern, VanityPosition::Prefix, true, format).is_ok());
+        }
+        assert!(VanityGenerator::new("5c", VanityPosition::Prefix, false, 42).is_ok());
+        for (pattern, position, format) in [
+            ("1zz", VanityPosition::Anywhere, 0),
+            (&"a".repeat(46), VanityPosition::Suffix, 42),
+            (&"2".repeat(47), VanityPosition::Suffix, 0),
+        ] {
+            assert!(VanityGenerator::new(pattern, position, true, format).is_ok());
+        }
+    }
+}
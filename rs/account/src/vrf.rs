@@ -0,0 +1,196 @@
+use error::Error;
+use merlin::Transcript;
+use schnorrkel::{
+    signing_context,
+    vrf::{VRFPreOut, VRFProof},
+    Keypair, PublicKey, SecretKey,
+};
+use wasm_bindgen::prelude::*;
+
+use crate::SigningTranscript;
+
+fn keypair(pubkey: &[u8], privkey: &[u8]) -> Result<Keypair, Error> {
+    match (
+        SecretKey::from_ed25519_bytes(privkey),
+        PublicKey::from_bytes(pubkey),
+    ) {
+        (Ok(s), Ok(k)) if s.to_public() == k => Ok(s.to_keypair()),
+        _ => Err(Error::InvalidKey),
+    }
+}
+
+fn sign(keypair: Keypair, transcript: Transcript) -> Vec<u8> {
+    let (io, proof, _) = keypair.vrf_sign(transcript);
+
+    let mut res = io.to_preout().to_bytes().to_vec();
+    res.extend_from_slice(&proof.to_bytes());
+    res
+}
+
+fn verify(pubkey: &[u8], transcript: Transcript, output: &[u8]) -> bool {
+    if output.len() != 96 {
+        return false;
+    }
+
+    match (
+        PublicKey::from_bytes(pubkey),
+        VRFPreOut::from_bytes(&output[..32]),
+        VRFProof::from_bytes(&output[32..]),
+    ) {
+        (Ok(k), Ok(o), Ok(p)) => k.vrf_verify(transcript, &o, &p).is_ok(),
+        _ => false,
+    }
+}
+
+/// Create a VRF output and proof for data in a signing context
+///
+/// * pubkey: 32-byte public key
+/// * privkey: 64-byte private key
+/// * label: label of the signing context (e.g. `b"substrate"`)
+/// * data: data to sign in the context
+///
+/// * Returns a 96-byte vector that is the result of appending the 64-byte proof to the end of the
+///   32-byte VRF output
+#[wasm_bindgen(js_name = vrfSign)]
+pub fn vrf_sign(
+    pubkey: &[u8],
+    privkey: &[u8],
+    label: &[u8],
+    data: &[u8],
+) -> Result<Vec<u8>, Error> {
+    Ok(sign(
+        keypair(pubkey, privkey)?,
+        signing_context(label).bytes(data),
+    ))
+}
+
+/// Verify a VRF output and proof for data in a signing context
+///
+/// * pubkey: 32-byte public key
+/// * label: label of the signing context
+/// * data: data that was signed in the context
+/// * output: 96-byte VRF output and proof
+///
+/// * Returns true if the VRF output and proof are valid for the data and public key
+#[wasm_bindgen(js_name = vrfVerify)]
+pub fn vrf_verify(pubkey: &[u8], label: &[u8], data: &[u8], output: &[u8]) -> bool {
+    verify(pubkey, signing_context(label).bytes(data), output)
+}
+
+/// Create a VRF output and proof for a transcript, such as the one that BABE uses to claim slots
+///
+/// A BABE transcript is labeled `BABE` and has the little-endian slot number, the little-endian
+/// epoch index and the epoch randomness appended as `slot number`, `current epoch` and
+/// `chain randomness`.
+///
+/// * pubkey: 32-byte public key
+/// * privkey: 64-byte private key
+/// * transcript: transcript to be signed
+///
+/// * Returns a 96-byte vector that is the result of appending the 64-byte proof to the end of the
+///   32-byte VRF output
+#[wasm_bindgen(js_name = vrfSignTranscript)]
+pub fn vrf_sign_transcript(
+    pubkey: &[u8],
+    privkey: &[u8],
+    transcript: &SigningTranscript,
+) -> Result<Vec<u8>, Error> {
+    Ok(sign(
+        keypair(pubkey, privkey)?,
+        transcript.transcript.clone(),
+    ))
+}
+
+/// Verify a VRF output and proof for a transcript
+///
+/// * pubkey: 32-byte public key
+/// * transcript: transcript that was signed
+/// * output: 96-byte VRF output and proof
+///
+/// * Returns true if the VRF output and proof are valid for the transcript and public key
+#[wasm_bindgen(js_name = vrfVerifyTranscript)]
+pub fn vrf_verify_transcript(pubkey: &[u8], transcript: &SigningTranscript, output: &[u8]) -> bool {
+    verify(pubkey, transcript.transcript.clone(), output)
+}
+
+#[cfg(test)]
+mod tests {
+    use merlin::Transcript;
+    use schnorrkel::{vrf::VRFPreOut, Keypair, PublicKey, SecretKey};
+
+    use crate::{
+        keypair_from_suri, vrf_sign, vrf_sign_transcript, vrf_verify, vrf_verify_transcript,
+        SigningTranscript,
+    };
+
+    #[test]
+    fn verifies_outputs() {
+        let alice = keypair_from_suri("//Alice").unwrap();
+        let bob = keypair_from_suri("//Bob").unwrap();
+
+        let output = vrf_sign(&alice[64..], &alice[..64], b"lottery", b"round 1").unwrap();
+        let again = vrf_sign(&alice[64..], &alice[..64], b"lottery", b"round 1").unwrap();
+
+        // the output is deterministic, but the proof is not
+        assert_eq!(output[..32], again[..32]);
+        assert!(vrf_verify(&alice[64..], b"lottery", b"round 1", &output));
+        assert!(!vrf_verify(&alice[64..], b"lottery", b"round 2", &output));
+        assert!(!vrf_verify(&bob[64..], b"lottery", b"round 1", &output));
+    }
+
+    #[test]
+    fn claims_babe_slots() {
+        let alice = keypair_from_suri("//Alice").unwrap();
+        let (slot, epoch, randomness) = (271_828_182u64, 42u64, [7u8; 32]);
+
+        let mut transcript = SigningTranscript::new(b"BABE").unwrap();
+        transcript
+            .append(b"slot number", &slot.to_le_bytes())
+            .unwrap();
+        transcript
+            .append(b"current epoch", &epoch.to_le_bytes())
+            .unwrap();
+        transcript.append(b"chain randomness", &randomness).unwrap();
+        let output = vrf_sign_transcript(&alice[64..], &alice[..64], &transcript).unwrap();
+
+        // `sp_consensus_babe::make_transcript`
+        let mut babe = Transcript::new(b"BABE");
+        babe.append_u64(b"slot number", slot);
+        babe.append_u64(b"current epoch", epoch);
+        babe.append_message(b"chain randomness", &randomness);
+
+        let keypair = Keypair {
+            secret: SecretKey::from_ed25519_bytes(&alice[..64]).unwrap(),
+            public: PublicKey::from_bytes(&alice[64..]).unwrap(),
+        };
+        let (io, _, _) = keypair.vrf_sign(babe.clone());
+        assert_eq!(output[..32], io.to_preout().to_bytes());
+
+        // the output is deterministic, so Alice's claim of this slot is pinned
+        assert_eq!(
+            hex::encode(&output[..32]),
+            "e2aac9770e9d507ef2ef9027e4d7eb6637af22d3fb0ab42e95ba3bd18f94632f"
+        );
+
+        // the bytes that BABE compares with its threshold
+        let preout = VRFPreOut::from_bytes(&output[..32]).unwrap();
+        let inout = preout.attach_input_hash(&keypair.public, babe).unwrap();
+        assert_eq!(
+            hex::encode(inout.make_bytes::<[u8; 16]>(b"substrate-babe-vrf")),
+            "c15d6ebcdd7c14bd2f18830405e8902c"
+        );
+
+        assert!(vrf_verify_transcript(&alice[64..], &transcript, &output));
+        assert!(!vrf_verify(
+            &alice[64..],
+            b"BABE",
+            &slot.to_le_bytes(),
+            &output
+        ));
+
+        transcript
+            .append(b"slot number", &(slot + 1).to_le_bytes())
+            .unwrap();
+        assert!(!vrf_verify_transcript(&alice[64..], &transcript, &output));
+    }
+}
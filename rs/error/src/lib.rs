@@ -0,0 +1,172 @@
+use std::fmt;
+
+use wasm_bindgen::prelude::*;
+
+/// The ways in which the functions that are exported by this project's Wasm libraries may fail
+///
+/// When returned to JavaScript, an `Error` becomes a JavaScript `Error` whose `message` is the
+/// `Display` representation of the variant and whose `code` is the name of the variant.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The bip39 phrase is not valid
+    InvalidPhrase,
+    /// The word at the given (1-based) position of the bip39 phrase is not in the wordlist
+    InvalidWord(usize),
+    /// The bip39 phrase does not have a valid number of words
+    InvalidWordCount,
+    /// The checksum of the bip39 phrase does not match its entropy
+    InvalidPhraseChecksum,
+    /// The entropy is not a valid length for a bip39 phrase
+    InvalidEntropy,
+    /// The secret (i.e. seed) is not valid
+    InvalidSecret,
+    /// The secret URI is malformed
+    InvalidSecretUri,
+    /// The derivation path contains a junction that the key type does not support
+    UnsupportedDerivation,
+    /// The public or private key is not valid
+    InvalidKey,
+    /// The Ss58 format is outside of the range of supported formats
+    UnsupportedNetwork,
+    /// The address is not valid base58
+    InvalidBase58,
+    /// The decoded address is not the expected length
+    InvalidAddressLength,
+    /// The checksum of the address does not match its contents
+    InvalidChecksum,
+    /// The address uses a reserved prefix
+    UnknownPrefix,
+    /// The address encodes a format below 64, which has a one-byte prefix, with two bytes
+    InvalidPrefix,
+    /// The key type ID is not four ASCII characters
+    InvalidKeyTypeId,
+    /// The operation is not supported for the key type
+    UnsupportedKeyType,
+    /// The ciphertext is malformed or was not encrypted for the given key
+    InvalidCiphertext,
+    /// The MuSig operation is not allowed at the current stage of the session
+    InvalidMuSigStage,
+    /// Another cosigner's MuSig commitment, reveal or cosignature is malformed or inconsistent
+    InvalidContribution,
+    /// The derivation path is malformed
+    InvalidPath,
+    /// Too many distinct transcript labels have been used
+    TooManyLabels,
+    /// The vanity pattern cannot appear in an Ss58 address of the given format
+    InvalidPattern,
+    /// The pallet ID is not 8 bytes
+    InvalidPalletId,
+    /// The threshold or number of shares of a split phrase is out of range
+    InvalidThreshold,
+    /// The share of a split phrase is malformed or inconsistent with the other shares
+    InvalidShare,
+    /// Fewer distinct shares were given than the threshold of the split phrase
+    InsufficientShares,
+    /// The keyring does not hold an account with the given handle
+    UnknownAccount,
+    /// The keystore JSON is malformed or uses an unsupported encoding
+    InvalidKeystore,
+    /// The password does not decrypt the keystore
+    InvalidPassword,
+    /// The metadata bytes could not be decoded
+    InvalidMetadata,
+    /// The metadata is a version other than V14
+    UnsupportedMetadataVersion,
+}
+
+impl Error {
+    /// A stable identifier for the error
+    pub fn code(&self) -> &'static str {
+        match self {
+            Error::InvalidPhrase => "InvalidPhrase",
+            Error::InvalidWord(_) => "InvalidWord",
+            Error::InvalidWordCount => "InvalidWordCount",
+            Error::InvalidPhraseChecksum => "InvalidPhraseChecksum",
+            Error::InvalidEntropy => "InvalidEntropy",
+            Error::InvalidSecret => "InvalidSecret",
+            Error::InvalidSecretUri => "InvalidSecretUri",
+            Error::UnsupportedDerivation => "UnsupportedDerivation",
+            Error::InvalidKey => "InvalidKey",
+            Error::UnsupportedNetwork => "UnsupportedNetwork",
+            Error::InvalidBase58 => "InvalidBase58",
+            Error::InvalidAddressLength => "InvalidAddressLength",
+            Error::InvalidChecksum => "InvalidChecksum",
+            Error::UnknownPrefix => "UnknownPrefix",
+            Error::InvalidPrefix => "InvalidPrefix",
+            Error::InvalidKeyTypeId => "InvalidKeyTypeId",
+            Error::UnsupportedKeyType => "UnsupportedKeyType",
+            Error::InvalidCiphertext => "InvalidCiphertext",
+            Error::InvalidMuSigStage => "InvalidMuSigStage",
+            Error::InvalidContribution => "InvalidContribution",
+            Error::InvalidPath => "InvalidPath",
+            Error::TooManyLabels => "TooManyLabels",
+            Error::InvalidPattern => "InvalidPattern",
+            Error::InvalidPalletId => "InvalidPalletId",
+            Error::InvalidThreshold => "InvalidThreshold",
+            Error::InvalidShare => "InvalidShare",
+            Error::InsufficientShares => "InsufficientShares",
+            Error::UnknownAccount => "UnknownAccount",
+            Error::InvalidKeystore => "InvalidKeystore",
+            Error::InvalidPassword => "InvalidPassword",
+            Error::InvalidMetadata => "InvalidMetadata",
+            Error::UnsupportedMetadataVersion => "UnsupportedMetadataVersion",
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            Error::InvalidPhrase => "Invalid phrase",
+            Error::InvalidWord(position) => {
+                return write!(f, "Invalid word at position {}", position)
+            }
+            Error::InvalidWordCount => "Invalid number of words",
+            Error::InvalidPhraseChecksum => "Invalid phrase checksum",
+            Error::InvalidEntropy => "Invalid entropy length",
+            Error::InvalidSecret => "Invalid secret",
+            Error::InvalidSecretUri => "Invalid secret URI",
+            Error::UnsupportedDerivation => "Unsupported derivation",
+            Error::InvalidKey => "Invalid key",
+            Error::UnsupportedNetwork => "Unsupported network",
+            Error::InvalidBase58 => "Invalid base58 address",
+            Error::InvalidAddressLength => "Invalid address length",
+            Error::InvalidChecksum => "Invalid address checksum",
+            Error::UnknownPrefix => "Unknown address prefix",
+            Error::InvalidPrefix => "Invalid address prefix",
+            Error::InvalidKeyTypeId => "Invalid key type ID",
+            Error::UnsupportedKeyType => "Unsupported key type",
+            Error::InvalidCiphertext => "Invalid ciphertext",
+            Error::InvalidMuSigStage => "Invalid MuSig stage",
+            Error::InvalidContribution => "Invalid MuSig contribution",
+            Error::InvalidPath => "Invalid derivation path",
+            Error::TooManyLabels => "Too many labels",
+            Error::InvalidPattern => "Invalid pattern",
+            Error::InvalidPalletId => "Invalid pallet ID",
+            Error::InvalidThreshold => "Invalid threshold",
+            Error::InvalidShare => "Invalid share",
+            Error::InsufficientShares => "Insufficient shares",
+            Error::UnknownAccount => "Unknown account",
+            Error::InvalidKeystore => "Invalid keystore",
+            Error::InvalidPassword => "Invalid password",
+            Error::InvalidMetadata => "Invalid metadata bytes",
+            Error::UnsupportedMetadataVersion => "Unsupported metadata version",
+        };
+
+        write!(f, "{}", message)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<Error> for JsValue {
+    fn from(err: Error) -> Self {
+        let value = JsValue::from(JsError::from(err));
+        // setting a property on a freshly created `Error` object cannot fail
+        let _ = js_sys::Reflect::set(&value, &"code".into(), &err.code().into());
+        if let Error::InvalidWord(position) = err {
+            let _ = js_sys::Reflect::set(&value, &"position".into(), &(position as u32).into());
+        }
+        value
+    }
+}
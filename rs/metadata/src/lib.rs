@@ -1,5 +1,7 @@
 use std::collections::BTreeMap;
 
+use error::Error;
+
 use frame_metadata::{
     ExtrinsicMetadata, PalletCallMetadata, PalletConstantMetadata, PalletErrorMetadata,
     PalletEventMetadata, PalletMetadata, PalletStorageMetadata, RuntimeMetadataV14,
@@ -149,8 +151,10 @@ impl From<&TypeDefCompact<PortableForm>> for ScaleType {
     }
 }
 
-impl From<&TypeDefVariant<PortableForm>> for ScaleType {
-    fn from(raw: &TypeDefVariant<PortableForm>) -> Self {
+impl TryFrom<&TypeDefVariant<PortableForm>> for ScaleType {
+    type Error = Error;
+
+    fn try_from(raw: &TypeDefVariant<PortableForm>) -> Result<Self, Error> {
         let variants = raw.variants();
         if variants.len() == 2 {
             let first = variants[0].name();
@@ -162,7 +166,7 @@ impl From<&TypeDefVariant<PortableForm>> for ScaleType {
             }
         }
 
-        ScaleType {
+        Ok(ScaleType {
             ty: PrimitiveScaleType::Enum,
             variants: Some(raw.variants().into_iter().map(|v| v.into()).collect()),
             store: None,
@@ -170,7 +174,7 @@ impl From<&TypeDefVariant<PortableForm>> for ScaleType {
             fields: None,
             order: None,
             name: None,
-        }
+        })
     }
 }
 
@@ -250,26 +254,39 @@ impl From<&TypeDefTuple<PortableForm>> for ScaleType {
     }
 }
 
+/// Resolve a type that the metadata references, which malformed metadata may not define
+fn resolve(id: u32, types: &PortableRegistry) -> Result<&scale_info::Type<PortableForm>, Error> {
+    types.resolve(id).ok_or(Error::InvalidMetadata)
+}
+
 impl ScaleType {
-    fn new(id: u32, types: &PortableRegistry) -> Self {
-        let raw_type = types
-            .resolve(id)
-            .expect("the metadata defines types it references");
+    fn new(id: u32, types: &PortableRegistry) -> Result<Self, Error> {
+        let raw_type = resolve(id, types)?;
+
+        // single-field composites are unwrapped, but keep the name of the outermost type; a cycle
+        // of them can only occur in malformed metadata, which would otherwise never terminate
+        let mut inner = raw_type;
+        let mut unwrapped = 0;
+        while let TypeDef::Composite(c) = inner.type_def() {
+            if c.fields().len() != 1 {
+                break;
+            }
+            if unwrapped == types.types().len() {
+                return Err(Error::InvalidMetadata);
+            }
+
+            inner = resolve(c.fields()[0].ty().id(), types)?;
+            unwrapped += 1;
+        }
 
-        let mut scale_type = match raw_type.type_def() {
+        let mut scale_type: ScaleType = match inner.type_def() {
             TypeDef::Primitive(p) => p.into(),
             TypeDef::Compact(c) => c.into(),
-            TypeDef::Variant(v) => v.into(),
+            TypeDef::Variant(v) => v.try_into()?,
             TypeDef::Array(a) => a.into(),
             TypeDef::Sequence(s) => s.into(),
             TypeDef::BitSequence(b) => b.into(),
-            TypeDef::Composite(c) => {
-                if c.fields().len() == 1 {
-                    ScaleType::new(c.fields()[0].ty().id(), types)
-                } else {
-                    c.into()
-                }
-            }
+            TypeDef::Composite(c) => c.into(),
             TypeDef::Tuple(t) => t.into(),
         };
 
@@ -292,35 +309,43 @@ impl ScaleType {
             _ => (),
         }
 
-        scale_type
+        Ok(scale_type)
     }
 
-    fn from_option(variant: &TypeDefVariant<PortableForm>) -> Self {
-        ScaleType {
+    /// The type of the only field of a variant
+    fn variant_field(variant: &scale_info::Variant<PortableForm>) -> Result<u32, Error> {
+        match variant.fields() {
+            [field] => Ok(field.ty().id()),
+            _ => Err(Error::InvalidMetadata),
+        }
+    }
+
+    fn from_option(variant: &TypeDefVariant<PortableForm>) -> Result<Self, Error> {
+        Ok(ScaleType {
             ty: PrimitiveScaleType::Option,
             variants: None,
-            store: Some(variant.variants()[1].fields()[0].ty().id()),
+            store: Some(Self::variant_field(&variant.variants()[1])?),
             length: None,
             fields: None,
             order: None,
             name: None,
-        }
+        })
     }
 
-    fn from_result(variant: &TypeDefVariant<PortableForm>) -> Self {
+    fn from_result(variant: &TypeDefVariant<PortableForm>) -> Result<Self, Error> {
         let variants = variant.variants();
         let fields = Some(vec![
             Field {
                 name: None,
-                field: variants[0].fields()[0].ty().id(),
+                field: Self::variant_field(&variants[0])?,
             },
             Field {
                 name: None,
-                field: variants[1].fields()[0].ty().id(),
+                field: Self::variant_field(&variants[1])?,
             },
         ]);
 
-        ScaleType {
+        Ok(ScaleType {
             ty: PrimitiveScaleType::Result,
             variants: None,
             store: None,
@@ -328,7 +353,7 @@ impl ScaleType {
             fields,
             order: None,
             name: None,
-        }
+        })
     }
 }
 
@@ -434,15 +459,18 @@ impl FromIterator<Event> for Events {
     }
 }
 
-impl From<(PalletEventMetadata<PortableForm>, &PortableRegistry)> for Events {
-    fn from(raw: (PalletEventMetadata<PortableForm>, &PortableRegistry)) -> Self {
-        let raw_type = raw
-            .1
-            .resolve(raw.0.ty.id())
-            .expect("the metadata defines types it references");
+impl TryFrom<(PalletEventMetadata<PortableForm>, &PortableRegistry)> for Events {
+    type Error = Error;
 
-        if let TypeDef::Variant(e) = raw_type.type_def() {
-            e.variants()
+    fn try_from(
+        raw: (PalletEventMetadata<PortableForm>, &PortableRegistry),
+    ) -> Result<Self, Error> {
+        let raw_type = resolve(raw.0.ty.id(), raw.1)?;
+
+        // Event metadata must be a Variant type
+        match raw_type.type_def() {
+            TypeDef::Variant(e) => Ok(e
+                .variants()
                 .into_iter()
                 .map(|v| Event {
                     index: v.index(),
@@ -450,9 +478,8 @@ impl From<(PalletEventMetadata<PortableForm>, &PortableRegistry)> for Events {
                     fields: v.fields().into_iter().map(|f| f.into()).collect(),
                     docs: v.docs().to_vec(),
                 })
-                .collect()
-        } else {
-            panic!("Event metadata must be a Variant type");
+                .collect()),
+            _ => Err(Error::InvalidMetadata),
         }
     }
 }
@@ -475,24 +502,26 @@ impl FromIterator<Err> for Errors {
     }
 }
 
-impl From<(PalletErrorMetadata<PortableForm>, &PortableRegistry)> for Errors {
-    fn from(raw: (PalletErrorMetadata<PortableForm>, &PortableRegistry)) -> Self {
-        let raw_type = raw
-            .1
-            .resolve(raw.0.ty.id())
-            .expect("the metadata defines types it references");
+impl TryFrom<(PalletErrorMetadata<PortableForm>, &PortableRegistry)> for Errors {
+    type Error = Error;
+
+    fn try_from(
+        raw: (PalletErrorMetadata<PortableForm>, &PortableRegistry),
+    ) -> Result<Self, Error> {
+        let raw_type = resolve(raw.0.ty.id(), raw.1)?;
 
-        if let TypeDef::Variant(e) = raw_type.type_def() {
-            e.variants()
+        // Error metadata must be a Variant type
+        match raw_type.type_def() {
+            TypeDef::Variant(e) => Ok(e
+                .variants()
                 .into_iter()
                 .map(|v| Err {
                     index: v.index(),
                     name: v.name().to_string(),
                     docs: v.docs().to_vec(),
                 })
-                .collect()
-        } else {
-            panic!("Error metadata must be a Variant type");
+                .collect()),
+            _ => Err(Error::InvalidMetadata),
         }
     }
 }
@@ -516,15 +545,16 @@ impl FromIterator<Call> for Calls {
     }
 }
 
-impl From<(PalletCallMetadata<PortableForm>, &PortableRegistry)> for Calls {
-    fn from(raw: (PalletCallMetadata<PortableForm>, &PortableRegistry)) -> Self {
-        let raw_type = raw
-            .1
-            .resolve(raw.0.ty.id())
-            .expect("the metadata defines types it references");
+impl TryFrom<(PalletCallMetadata<PortableForm>, &PortableRegistry)> for Calls {
+    type Error = Error;
+
+    fn try_from(raw: (PalletCallMetadata<PortableForm>, &PortableRegistry)) -> Result<Self, Error> {
+        let raw_type = resolve(raw.0.ty.id(), raw.1)?;
 
-        if let TypeDef::Variant(e) = raw_type.type_def() {
-            e.variants()
+        // Call metadata must be a Variant type
+        match raw_type.type_def() {
+            TypeDef::Variant(e) => Ok(e
+                .variants()
                 .into_iter()
                 .map(|v| Call {
                     index: v.index(),
@@ -532,9 +562,8 @@ impl From<(PalletCallMetadata<PortableForm>, &PortableRegistry)> for Calls {
                     fields: v.fields().into_iter().map(|f| f.into()).collect(),
                     docs: v.docs().to_vec(),
                 })
-                .collect()
-        } else {
-            panic!("Call metadata must be a Variant type");
+                .collect()),
+            _ => Err(Error::InvalidMetadata),
         }
     }
 }
@@ -551,17 +580,19 @@ struct Pallet {
     calls: Option<Calls>,
 }
 
-impl From<(PalletMetadata<PortableForm>, &PortableRegistry)> for Pallet {
-    fn from(raw: (PalletMetadata<PortableForm>, &PortableRegistry)) -> Self {
-        Pallet {
+impl TryFrom<(PalletMetadata<PortableForm>, &PortableRegistry)> for Pallet {
+    type Error = Error;
+
+    fn try_from(raw: (PalletMetadata<PortableForm>, &PortableRegistry)) -> Result<Self, Error> {
+        Ok(Pallet {
             index: raw.0.index,
             name: raw.0.name.to_string(),
             constants: raw.0.constants.into_iter().map(|c| c.into()).collect(),
             storage: raw.0.storage.map(|s| s.into()),
-            errors: raw.0.error.map(|e| (e, raw.1).into()),
-            events: raw.0.event.map(|e| (e, raw.1).into()),
-            calls: raw.0.calls.map(|c| (c, raw.1).into()),
-        }
+            errors: raw.0.error.map(|e| (e, raw.1).try_into()).transpose()?,
+            events: raw.0.event.map(|e| (e, raw.1).try_into()).transpose()?,
+            calls: raw.0.calls.map(|c| (c, raw.1).try_into()).transpose()?,
+        })
     }
 }
 
@@ -569,14 +600,16 @@ impl From<(PalletMetadata<PortableForm>, &PortableRegistry)> for Pallet {
 #[derive(Serialize)]
 struct Types(BTreeMap<u32, ScaleType>);
 
-impl From<PortableRegistry> for Types {
-    fn from(raw: PortableRegistry) -> Self {
+impl TryFrom<PortableRegistry> for Types {
+    type Error = Error;
+
+    fn try_from(raw: PortableRegistry) -> Result<Self, Error> {
         let mut idx = BTreeMap::new();
         for ty in raw.types() {
-            idx.insert(ty.id(), ScaleType::new(ty.id(), &raw));
+            idx.insert(ty.id(), ScaleType::new(ty.id(), &raw)?);
         }
 
-        Types(idx)
+        Ok(Types(idx))
     }
 }
 
@@ -593,15 +626,12 @@ impl SignedExtension {
     fn from_metadata(
         raw: SignedExtensionMetadata<PortableForm>,
         types: &PortableRegistry,
-    ) -> Option<Self> {
-        let raw_ty = types.resolve(raw.ty.id());
-        let raw_additional = types.resolve(raw.additional_signed.id());
-        if raw_ty.is_none() && raw_additional.is_none() {
-            return None;
-        }
+    ) -> Result<Option<Self>, Error> {
+        let raw_ty = resolve(raw.ty.id(), types)?;
+        let raw_additional = resolve(raw.additional_signed.id(), types)?;
 
         let mut ty = Option::<u32>::None;
-        match raw_ty.unwrap().type_def() {
+        match raw_ty.type_def() {
             TypeDef::Composite(c) => {
                 if c.fields().len() > 0 {
                     ty = Some(raw.ty.id());
@@ -626,7 +656,7 @@ impl SignedExtension {
         }
 
         let mut additional = Option::<u32>::None;
-        match raw_additional.unwrap().type_def() {
+        match raw_additional.type_def() {
             TypeDef::Composite(c) => {
                 if c.fields().len() > 0 {
                     additional = Some(raw.additional_signed.id());
@@ -651,14 +681,14 @@ impl SignedExtension {
         }
 
         if ty.is_none() && additional.is_none() {
-            return None;
+            return Ok(None);
         }
 
-        Some(SignedExtension {
+        Ok(Some(SignedExtension {
             ty,
             additional,
             name: raw.identifier,
-        })
+        }))
     }
 }
 
@@ -670,20 +700,22 @@ struct Extensions {
     extensions: Vec<SignedExtension>,
 }
 
-impl From<(ExtrinsicMetadata<PortableForm>, &PortableRegistry)> for Extensions {
-    fn from(raw: (ExtrinsicMetadata<PortableForm>, &PortableRegistry)) -> Self {
+impl TryFrom<(ExtrinsicMetadata<PortableForm>, &PortableRegistry)> for Extensions {
+    type Error = Error;
+
+    fn try_from(raw: (ExtrinsicMetadata<PortableForm>, &PortableRegistry)) -> Result<Self, Error> {
         let mut extensions = Vec::new();
         for extension in raw.0.signed_extensions {
-            match SignedExtension::from_metadata(extension, raw.1) {
+            match SignedExtension::from_metadata(extension, raw.1)? {
                 None => (),
                 Some(e) => extensions.push(e),
             }
         }
 
-        Extensions {
+        Ok(Extensions {
             extensions,
             version: raw.0.version,
-        }
+        })
     }
 }
 
@@ -695,33 +727,35 @@ struct Metadata {
     signing: Extensions,
 }
 
-impl From<RuntimeMetadataV14> for Metadata {
-    fn from(raw: RuntimeMetadataV14) -> Self {
-        Metadata {
+impl TryFrom<RuntimeMetadataV14> for Metadata {
+    type Error = Error;
+
+    fn try_from(raw: RuntimeMetadataV14) -> Result<Self, Error> {
+        Ok(Metadata {
             pallets: raw
                 .pallets
                 .into_iter()
-                .map(|p| (p, &raw.types).into())
-                .collect(),
-            signing: (raw.extrinsic, &raw.types).into(),
-            types: raw.types.into(),
-        }
+                .map(|p| (p, &raw.types).try_into())
+                .collect::<Result<_, _>>()?,
+            signing: (raw.extrinsic, &raw.types).try_into()?,
+            types: raw.types.try_into()?,
+        })
     }
 }
 
 #[wasm_bindgen(js_name = metadataFromHex)]
-pub fn metadata_from_hex(hex: &[u8]) -> JsValue {
+pub fn metadata_from_hex(hex: &[u8]) -> Result<JsValue, Error> {
     let pre = match frame_metadata::RuntimeMetadataPrefixed::decode(&mut &*hex) {
         Ok(m) => m.1,
-        _ => panic!("Invalid metadata bytes"),
+        _ => return Err(Error::InvalidMetadata),
     };
 
     let meta = match pre {
         frame_metadata::RuntimeMetadata::V14(m) => m,
-        _ => panic!("Unsupported metadata version"),
+        _ => return Err(Error::UnsupportedMetadataVersion),
     };
 
-    serde_wasm_bindgen::to_value(&Metadata::from(meta)).unwrap_or(Default::default())
+    serde_wasm_bindgen::to_value(&Metadata::try_from(meta)?).map_err(|_| Error::InvalidMetadata)
 }
 
 #[cfg(test)]
@@ -730,10 +764,11 @@ mod tests {
 
     use std::fs;
 
+    use error::Error;
+    use frame_metadata::RuntimeMetadataV14;
     use parity_scale_codec::Decode;
 
-    #[test]
-    fn it_works() {
+    fn westend() -> RuntimeMetadataV14 {
         let hex_str = fs::read_to_string(concat!(
             env!("CARGO_MANIFEST_DIR"),
             "/test-assets/westend-v0.9.29-meta.hex"
@@ -751,11 +786,45 @@ mod tests {
             _ => panic!("Invalid metadata bytes"),
         };
 
-        let meta = match pre {
+        match pre {
             frame_metadata::RuntimeMetadata::V14(m) => m,
             _ => panic!("Unsupported metadata version"),
-        };
+        }
+    }
+
+    #[test]
+    fn it_works() {
+        assert!(Metadata::try_from(westend()).is_ok());
+    }
+
+    #[test]
+    fn keeps_names_of_wrapped_types() {
+        let meta = Metadata::try_from(westend()).unwrap();
+        let extension = meta
+            .signing
+            .extensions
+            .iter()
+            .find(|e| e.name == "CheckMortality")
+            .unwrap();
+
+        // CheckMortality wraps an Era, whose encoding it shares but whose name it does not
+        assert_eq!(
+            meta.types.0[&extension.ty.unwrap()].name.as_deref(),
+            Some("frame_system::extensions::check_mortality::CheckMortality")
+        );
+    }
 
-        let _ = Metadata::from(meta);
+    #[test]
+    fn rejects_malformed_metadata() {
+        let mut meta = westend();
+        let pallet = meta
+            .pallets
+            .iter_mut()
+            .find(|p| p.event.is_some() && !p.constants.is_empty())
+            .unwrap();
+
+        // a constant's type is never a Variant type
+        pallet.event.as_mut().unwrap().ty = pallet.constants[0].ty;
+        assert_eq!(Metadata::try_from(meta).err(), Some(Error::InvalidMetadata));
     }
 }